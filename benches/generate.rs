@@ -84,6 +84,20 @@ fn bench_generate_i128(b: &mut Bencher) {
     generate_points::<i128>(b);
 }
 
+/// Generates N `f64` points into a reused buffer
+#[bench]
+fn bench_generate_into_f64(b: &mut Bencher) {
+    let sobol = Sobol::<f64>::new(DIMS, &JoeKuoD6::STANDARD).unwrap();
+    let mut buf = [0f64; DIMS];
+    b.iter(|| {
+        let mut sobol = sobol.clone();
+        for _ in 0..N {
+            sobol.next_into(&mut buf);
+            black_box(&buf);
+        }
+    });
+}
+
 /// Generates N points for a given type and consumes the results.
 fn generate_points<T>(b: &mut Bencher)
 where
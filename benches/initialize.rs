@@ -3,8 +3,8 @@ extern crate test;
 
 use test::{Bencher, black_box};
 
-use sobol_qmc::Sobol;
 use sobol_qmc::params::JoeKuoD6;
+use sobol_qmc::{DirectionTable, Sobol, UnitRender};
 
 /// The dimensionality of the sequence to generate
 const DIMS: usize = 512;
@@ -43,3 +43,13 @@ fn bench_initialize_u128(b: &mut Bencher) {
     let params = JoeKuoD6::STANDARD;
     b.iter(|| black_box(Sobol::<u128>::new(DIMS, &params)));
 }
+
+/// Constructs a 32-bit sequence from a cached direction table
+#[bench]
+fn bench_initialize_cached_u32(b: &mut Bencher) {
+    let params = JoeKuoD6::STANDARD;
+    b.iter(|| {
+        let table = DirectionTable::<u32>::cached(DIMS, &params, None).unwrap();
+        black_box(Sobol::from_table(table, UnitRender))
+    });
+}
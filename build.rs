@@ -6,6 +6,9 @@ use std::{
     io::{BufRead, BufReader, Cursor, Write},
 };
 
+#[path = "src/params/matrix_text.rs"]
+mod matrix_text;
+
 fn gen_joe_kuo_d6() {
     pub struct JoeKuoD6 {
         pub dim_params: Vec<JoeKuoD6Dim>,
@@ -30,28 +33,38 @@ fn gen_joe_kuo_d6() {
         }
     }
 
-    impl ToTokens for JoeKuoD6 {
+    impl ToTokens for JoeKuoD6Dim {
         fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
-            let dim_params: Vec<_> = self
-                .dim_params
-                .iter()
-                .map(|JoeKuoD6Dim { d, a, m }| {
-                    quote! {
-                        JoeKuoD6Dim {
-                            d: #d,
-                            a: #a,
-                            m: &[#(#m),*],
-                        }
-                    }
-                })
-                .collect();
-            let max_dims = self.max_dims;
+            let JoeKuoD6Dim { d, a, m } = self;
             tokens.extend(quote! {
+                JoeKuoD6Dim {
+                    d: #d,
+                    a: #a,
+                    m: &[#(#m),*],
+                }
+            });
+        }
+    }
+
+    impl JoeKuoD6 {
+        /// A constant using the leading dimensions of the embedded table, which must be a
+        /// prefix of the table
+        fn to_prefix_tokens(&self, table: &JoeKuoD6) -> TokenStream {
+            let len = self.dim_params.len();
+            assert!(
+                table.dim_params[..len]
+                    .iter()
+                    .zip(&self.dim_params)
+                    .all(|(t, p)| (t.d, t.a, &t.m) == (p.d, p.a, &p.m)),
+                "embedded parameter sets must be prefixes of the largest one"
+            );
+            let max_dims = self.max_dims;
+            quote! {
                 JoeKuoD6 {
-                    dim_params: &[#(#dim_params),*],
+                    dim_params: JOE_KUO_D6_DIMS.split_at(#len).0,
                     max_dims: #max_dims,
                 }
-            });
+            }
         }
     }
 
@@ -87,11 +100,19 @@ fn gen_joe_kuo_d6() {
         env!("CARGO_MANIFEST_DIR"),
         "/src/params/data/new-joe-kuo-6.21201.gz"
     )));
+    let len = p21201.dim_params.len();
+    let dims = &p21201.dim_params;
+    let (minimal, standard, extended) = (
+        p100.to_prefix_tokens(&p21201),
+        p1000.to_prefix_tokens(&p21201),
+        p21201.to_prefix_tokens(&p21201),
+    );
     file.write_all(
         quote! {
-            const MINIMAL: JoeKuoD6 = #p100;
-            const STANDARD: JoeKuoD6 = #p1000;
-            const EXTENDED: JoeKuoD6 = #p21201;
+            static JOE_KUO_D6_DIMS: [JoeKuoD6Dim; #len] = [#(#dims),*];
+            const MINIMAL: JoeKuoD6 = #minimal;
+            const STANDARD: JoeKuoD6 = #standard;
+            const EXTENDED: JoeKuoD6 = #extended;
         }
         .to_string()
         .as_bytes(),
@@ -149,7 +170,110 @@ fn gen_ref_seq() {
     .expect("Could not write file");
 }
 
+fn gen_golden() {
+    /// A golden point: type name, resolution (0 for full), seed, index and integer values
+    type Golden = (String, usize, u64, u64, Vec<u128>);
+    fn load_golden(filename: &str) -> Vec<Golden> {
+        if let Ok(mut file) = File::open(filename) {
+            let mut decoder = Decoder::new(&mut file).unwrap();
+            BufReader::new(&mut decoder)
+                .lines()
+                .map(|res| res.unwrap())
+                .filter(|l| !l.starts_with('#'))
+                .map(|l| {
+                    let mut cols = l.split('\t');
+                    let mut col = || cols.next().unwrap().to_string();
+                    let (ty, res, seed, index) = (col(), col(), col(), col());
+                    let vals = col()
+                        .split_whitespace()
+                        .map(|v| v.parse::<u128>().unwrap())
+                        .collect();
+                    (
+                        ty,
+                        res.parse().unwrap(),
+                        seed.parse().unwrap(),
+                        index.parse().unwrap(),
+                        vals,
+                    )
+                })
+                .collect()
+        } else {
+            // for release crate package, no golden file
+            vec![]
+        }
+    }
+    fn golden2token(golden: Vec<Golden>) -> TokenStream {
+        let tokens: Vec<_> = golden
+            .into_iter()
+            .map(|(ty, res, seed, index, vals)| quote! {(#ty, #res, #seed, #index, &[#(#vals),*])})
+            .collect();
+        quote! {&[#(#tokens),*]}
+    }
+    let out_dir = std::env::var("OUT_DIR").unwrap();
+    let dest_path = std::path::Path::new(&out_dir).join("gen_golden.rs");
+    let mut file = File::create(&dest_path).expect("Could not create file");
+    let v1 = golden2token(load_golden(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/data/scramble_v1.tsv.gz"
+    )));
+    file.write_all(
+        quote! {
+            const SCRAMBLE_V1: &[(&str, usize, u64, u64, &[u128])] = #v1;
+        }
+        .to_string()
+        .as_bytes(),
+    )
+    .expect("Could not write file");
+}
+
+fn gen_niederreiter_xing() {
+    let dir = concat!(env!("CARGO_MANIFEST_DIR"), "/src/params/data/nx");
+    let mut files: Vec<_> = std::fs::read_dir(dir)
+        .expect("Could not read directory")
+        .map(|e| e.unwrap().path())
+        .filter(|p| p.extension().is_some_and(|ext| ext == "gz"))
+        .collect();
+    files.sort();
+    let tables: Vec<_> = files
+        .iter()
+        .map(|path| {
+            let mut file = File::open(path).unwrap();
+            let mut text = String::new();
+            std::io::Read::read_to_string(&mut Decoder::new(&mut file).unwrap(), &mut text)
+                .unwrap();
+            // Same validation as `GeneratorMatrices::parse`, which reads the same format
+            let (dims, columns, precision, data) = matrix_text::parse_matrix_text(&text)
+                .unwrap_or_else(|line| {
+                    panic!("Malformed generator matrices on line {line} of {path:?}")
+                });
+            quote! {
+                GeneratorMatrices {
+                    dims: #dims,
+                    columns: #columns,
+                    precision: #precision,
+                    data: Cow::Borrowed(&[#(#data),*]),
+                }
+            }
+        })
+        .collect();
+    let out_dir = std::env::var("OUT_DIR").unwrap();
+    let dest_path = std::path::Path::new(&out_dir).join("gen_niederreiter_xing.rs");
+    let mut file = File::create(&dest_path).expect("Could not create file");
+    file.write_all(
+        quote! {
+            const NIEDERREITER_XING: &[GeneratorMatrices] = &[#(#tables),*];
+        }
+        .to_string()
+        .as_bytes(),
+    )
+    .expect("Could not write file");
+}
+
 fn main() {
     gen_joe_kuo_d6();
     gen_ref_seq();
+    gen_golden();
+    if std::env::var_os("CARGO_FEATURE_NIEDERREITER_XING").is_some() {
+        gen_niederreiter_xing();
+    }
 }
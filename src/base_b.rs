@@ -0,0 +1,379 @@
+use crate::{InternalType, Render, SobolError, SobolType, UnitRender, scramble::resolution_mask};
+use num_traits::ToPrimitive;
+use std::iter::FusedIterator;
+
+/// A digital sequence in an arbitrary base `b`: the base `b` digits of the point index are
+/// mapped through one generator matrix over `Z_b` per dimension, and the resulting digits are
+/// read as a base `b` fraction. This is the non-binary counterpart of `Sobol`, whose matrices
+/// act on bits by XOR; here digits are combined by multiplication and addition modulo `b`.
+///
+/// Matrices have as many rows and columns as it takes base `b` digits to write any 64-bit
+/// index, so values carry at least 64 bits of precision before being rounded down to the
+/// resolution of `T` and rendered by the same `Render`s as `Sobol`.
+pub struct BaseBSequence<T: SobolType, R: Render<T> = UnitRender> {
+    dims: usize,
+    base: u32,
+    resolution: usize,
+    /// The index of the next point, from which `index_digits` and `outputs` are derived
+    count: u64,
+    /// The number of base `b` digits of indices and values
+    digits: usize,
+    /// Matrix entries, column by column: row `k` of column `r` of dimension `d` is found at
+    /// `(d * digits + r) * digits + k`
+    cols: Vec<u32>,
+    /// The digits of `count`, least significant first
+    index_digits: Vec<u32>,
+    /// The output digits of the point at `count`, `digits` per dimension
+    outputs: Vec<u32>,
+    render: R,
+    mask: T::IT,
+}
+
+impl<T: SobolType, R: Render<T>> Clone for BaseBSequence<T, R> {
+    fn clone(&self) -> Self {
+        BaseBSequence {
+            dims: self.dims,
+            base: self.base,
+            resolution: self.resolution,
+            count: self.count,
+            digits: self.digits,
+            cols: self.cols.clone(),
+            index_digits: self.index_digits.clone(),
+            outputs: self.outputs.clone(),
+            render: self.render.clone(),
+            mask: self.mask,
+        }
+    }
+}
+
+impl<T: SobolType> BaseBSequence<T, UnitRender>
+where
+    UnitRender: Render<T>,
+{
+    /// Constructs a Faure sequence, see `new_faure`
+    pub fn faure(dims: usize) -> Self {
+        Self::new_faure(dims, None, UnitRender)
+            .expect("UnitRender supports any number of dimensions")
+    }
+}
+
+impl<T: SobolType, R: Render<T>> BaseBSequence<T, R> {
+    /// Constructs a Faure sequence: a (0,s)-sequence in the smallest prime base `b >= dims`,
+    /// whose `j`-th dimension (counting from zero) uses the `j`-th power of the upper
+    /// triangular Pascal matrix, with entries `C(r, k) j^(r - k) mod b`. Resolution defaults to
+    /// (and is capped at) `T::MAX_RESOLUTION`.
+    pub fn new_faure(
+        dims: usize,
+        resolution: Option<usize>,
+        render: R,
+    ) -> Result<Self, SobolError> {
+        let base = (dims.max(2) as u32..)
+            .find(|n| (2..*n).take_while(|p| p * p <= *n).all(|p| n % p != 0))
+            .expect("a prime base exists");
+        let b = base as u64;
+        let m = digit_count(base);
+
+        // Binomial coefficients modulo the base, `binomial[r][k] = C(r, k)`
+        let mut binomial = vec![vec![0u64; m]; m];
+        for r in 0..m {
+            binomial[r][0] = 1;
+            for k in 1..=r {
+                binomial[r][k] = (binomial[r - 1][k - 1] + binomial[r - 1][k]) % b;
+            }
+        }
+        let matrices: Vec<Vec<Vec<u32>>> = (0..dims as u64)
+            .map(|j| {
+                (0..m)
+                    .map(|r| {
+                        let mut power = 1;
+                        let mut col = vec![0; m];
+                        for k in (0..=r).rev() {
+                            col[k] = (binomial[r][k] * power % b) as u32;
+                            power = power * j % b;
+                        }
+                        col
+                    })
+                    .collect()
+            })
+            .collect();
+        Self::from_matrices(base, &matrices, resolution, render)
+    }
+
+    /// Constructs a digital sequence in the given base from explicit generator matrices over
+    /// `Z_b`. `matrices[dim][r][k]` is the entry in row `k` (output digit `k`, most significant
+    /// first) of column `r` (index digit `r`, least significant first). Each matrix must be
+    /// square, with as many digits as `base_digits(base)`; entries are reduced modulo `base`.
+    pub fn from_matrices(
+        base: u32,
+        matrices: &[Vec<Vec<u32>>],
+        resolution: Option<usize>,
+        render: R,
+    ) -> Result<Self, SobolError> {
+        let dims = matrices.len();
+        if let Some(render_dims) = render.support_dims()
+            && dims != render_dims
+        {
+            return Err(SobolError::RenderDim { dims, render_dims });
+        }
+        if base < 2 {
+            return Err(SobolError::InvalidBase { base });
+        }
+        let m = digit_count(base);
+        let mut cols = Vec::with_capacity(dims * m * m);
+        for (dim, matrix) in matrices.iter().enumerate() {
+            match matrix.iter().position(|col| col.len() != m) {
+                Some(column) => {
+                    return Err(SobolError::DigitRows {
+                        dim,
+                        column,
+                        rows: matrix[column].len(),
+                        digits: m,
+                    });
+                }
+                None if matrix.len() != m => {
+                    return Err(SobolError::DigitColumns {
+                        dim,
+                        columns: matrix.len(),
+                        digits: m,
+                    });
+                }
+                None => cols.extend(matrix.iter().flatten().map(|e| e % base)),
+            }
+        }
+        let res = resolution
+            .filter(|res| *res <= T::MAX_RESOLUTION)
+            .unwrap_or(T::MAX_RESOLUTION);
+        Ok(BaseBSequence {
+            dims,
+            base,
+            resolution: res,
+            count: 0,
+            digits: m,
+            cols,
+            index_digits: vec![0; m],
+            outputs: vec![0; dims * m],
+            render,
+            mask: resolution_mask(res),
+        })
+    }
+
+    /// The number of base `b` digits of the indices and values of a sequence in the given base
+    pub fn base_digits(base: u32) -> usize {
+        digit_count(base)
+    }
+
+    /// The number of dimensions of each point
+    #[inline]
+    pub fn dims(&self) -> usize {
+        self.dims
+    }
+
+    /// The base `b` of the digits of indices and values
+    #[inline]
+    pub fn base(&self) -> u32 {
+        self.base
+    }
+
+    /// The number of bits of each value
+    #[inline]
+    pub fn resolution(&self) -> usize {
+        self.resolution
+    }
+
+    /// Returns the zero-based index of the point the next call to `next()` will yield
+    #[inline]
+    pub fn position(&self) -> u64 {
+        self.count
+    }
+
+    /// Returns the point at the given zero-based index, or `None` if the index is beyond the
+    /// end of the sequence. Does not affect the position of the sequence.
+    pub fn point_at(&self, index: u64) -> Option<Vec<T>> {
+        if index < u64::MAX {
+            let outputs = self.outputs_at(index);
+            Some(self.render_digits(&outputs).collect())
+        } else {
+            None
+        }
+    }
+
+    /// Same as `point_at` but writes the point into the given buffer. Returns `false` (leaving
+    /// the buffer untouched) if the index is beyond the end of the sequence.
+    ///
+    /// Panics if the length of the buffer differs from `dims`.
+    pub fn point_at_into(&self, index: u64, out: &mut [T]) -> bool {
+        self.check_buf_len(out.len());
+        if index < u64::MAX {
+            let outputs = self.outputs_at(index);
+            for (o, v) in out.iter_mut().zip(self.render_digits(&outputs)) {
+                *o = v;
+            }
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Moves the sequence to the given zero-based index
+    pub fn seek(&mut self, index: u64) {
+        self.count = index;
+        self.index_digits = self.index_to_digits(index);
+        self.outputs = self.outputs_at(index);
+    }
+
+    /// Writes the next point into the given buffer rather than allocating a new `Vec` as
+    /// `next()` does. Returns `false` (leaving the buffer untouched) once the sequence is
+    /// exhausted.
+    ///
+    /// Panics if the length of the buffer differs from `dims`.
+    pub fn next_into(&mut self, out: &mut [T]) -> bool {
+        self.check_buf_len(out.len());
+        if self.count < u64::MAX {
+            for (o, v) in out.iter_mut().zip(self.render_digits(&self.outputs)) {
+                *o = v;
+            }
+            self.advance();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Moves to the next index, updating the output digits incrementally. Each index digit
+    /// that changes either increases by one or wraps from `b - 1` to zero, a change of `+1`
+    /// modulo `b` in both cases, so its matrix column is added to the outputs once.
+    fn advance(&mut self) {
+        let (b, m) = (self.base, self.digits);
+        for r in 0..m {
+            for dim in 0..self.dims {
+                let col = &self.cols[(dim * m + r) * m..(dim * m + r + 1) * m];
+                for (y, c) in self.outputs[dim * m..(dim + 1) * m].iter_mut().zip(col) {
+                    *y = ((*y as u64 + *c as u64) % b as u64) as u32;
+                }
+            }
+            self.index_digits[r] += 1;
+            if self.index_digits[r] < b {
+                break;
+            }
+            self.index_digits[r] = 0;
+        }
+        self.count += 1;
+    }
+
+    /// The base `b` digits of an index, least significant first
+    fn index_to_digits(&self, mut index: u64) -> Vec<u32> {
+        let b = self.base as u64;
+        (0..self.digits)
+            .map(|_| {
+                let digit = index % b;
+                index /= b;
+                digit as u32
+            })
+            .collect()
+    }
+
+    /// The output digits of every dimension of the point at the given index
+    fn outputs_at(&self, index: u64) -> Vec<u32> {
+        let (b, m) = (self.base as u64, self.digits);
+        let index_digits = self.index_to_digits(index);
+        let mut outputs = vec![0u32; self.dims * m];
+        for dim in 0..self.dims {
+            let y = &mut outputs[dim * m..(dim + 1) * m];
+            for (r, a) in index_digits.iter().enumerate().filter(|(_, a)| **a != 0) {
+                let col = &self.cols[(dim * m + r) * m..(dim * m + r + 1) * m];
+                for (y, c) in y.iter_mut().zip(col) {
+                    *y = ((*y as u64 + *c as u64 * *a as u64) % b) as u32;
+                }
+            }
+        }
+        outputs
+    }
+
+    /// Renders output digits, `digits` per dimension, into points
+    fn render_digits<'a>(&'a self, outputs: &'a [u32]) -> impl Iterator<Item = T> + 'a {
+        let b = self.base as u128;
+        outputs
+            .chunks(self.digits)
+            .enumerate()
+            .map(move |(dim, digits)| {
+                let (num, den) = digits.iter().fold((0u128, 1u128), |(num, den), d| {
+                    (num * b + *d as u128, den * b)
+                });
+                self.render
+                    .render(dim, fixed_point::<T::IT>(num, den) & self.mask)
+            })
+    }
+
+    fn check_buf_len(&self, len: usize) {
+        assert_eq!(
+            len, self.dims,
+            "buffer length must match the number of sequence dimensions"
+        );
+    }
+}
+
+impl<T: SobolType, R: Render<T>> Iterator for BaseBSequence<T, R> {
+    type Item = Vec<T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.count < u64::MAX {
+            let point = self.render_digits(&self.outputs).collect();
+            self.advance();
+            Some(point)
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.seek(self.count.saturating_add(n as u64));
+        self.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match (u64::MAX - self.count).to_usize() {
+            Some(len) => (len, Some(len)),
+            None => (usize::MAX, None),
+        }
+    }
+}
+
+/// Indices are 64-bit, so the remaining length only always fits a `usize` on 64-bit targets
+#[cfg(target_pointer_width = "64")]
+impl<T: SobolType, R: Render<T>> ExactSizeIterator for BaseBSequence<T, R> {}
+
+impl<T: SobolType, R: Render<T>> FusedIterator for BaseBSequence<T, R> {}
+
+/// The number of base `b` digits it takes to write any 64-bit index
+fn digit_count(base: u32) -> usize {
+    let mut power = 1u128;
+    let mut digits = 0;
+    while power <= u64::MAX as u128 {
+        power *= base as u128;
+        digits += 1;
+    }
+    digits
+}
+
+/// The fraction `num / den` (below one) as a fixed-point value of an internal type, rounded
+/// down. Both must stay below `2^96`, which leaves room for 32 bits of long division at a time.
+pub(crate) fn fixed_point<IT: InternalType>(mut num: u128, den: u128) -> IT {
+    let mut val = IT::zero();
+    let mut bits = 0;
+    while bits < IT::BITS {
+        let chunk = (IT::BITS - bits).min(32);
+        num <<= chunk;
+        let q = IT::lossy_from((num / den) as u64);
+        num %= den;
+        val = if chunk == IT::BITS {
+            q
+        } else {
+            (val << chunk) | q
+        };
+        bits += chunk;
+    }
+    val
+}
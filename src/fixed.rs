@@ -0,0 +1,180 @@
+use crate::{
+    ExactLenType, InternalType, LossyFrom, Render, SobolError, SobolParams, SobolType, UnitRender,
+    table::DirectionTable,
+};
+use core::array;
+use num_traits::{Bounded, NumCast, One, PrimInt, ToPrimitive, Zero};
+use std::iter::FusedIterator;
+use std::sync::Arc;
+
+/// A Sobol sequence generator for a fixed number of dimensions `D` known at compile time.
+/// Points are yielded as `[T; D]` arrays and the cursor state is held in a fixed-size array
+/// rather than on the heap. The direction values, one `[T::IT; D]` row per bit of resolution,
+/// are allocated once and shared between clones, so generators of many dimensions neither
+/// overflow the stack nor get expensive to clone.
+pub struct SobolN<T: SobolType, const D: usize, R: Render<T> = UnitRender> {
+    pub resolution: usize,
+    dir_vals: Arc<[[T::IT; D]]>,
+    previous: [T::IT; D],
+    render: R,
+    pub count: T::IT,
+    pub max_len: T::IT,
+}
+
+impl<T: SobolType, const D: usize, R: Render<T>> Clone for SobolN<T, D, R> {
+    fn clone(&self) -> Self {
+        SobolN {
+            resolution: self.resolution,
+            dir_vals: self.dir_vals.clone(),
+            previous: self.previous,
+            render: self.render.clone(),
+            count: self.count,
+            max_len: self.max_len,
+        }
+    }
+}
+
+impl<T: SobolType, const D: usize> SobolN<T, D, UnitRender>
+where
+    UnitRender: Render<T>,
+{
+    /// Constructs a new sequence
+    pub fn new<P, Param: SobolParams<P>>(params: &Param) -> Result<Self, SobolError>
+    where
+        T::IT: LossyFrom<P>,
+    {
+        Self::new_with_resolution::<P, Param>(params, None, UnitRender)
+    }
+}
+
+impl<T: SobolType, const D: usize, R: Render<T>> SobolN<T, D, R> {
+    /// Constructs a new sequence of given resolution. See `Sobol::new_with_resolution`.
+    pub fn new_with_resolution<P, Param: SobolParams<P>>(
+        params: &Param,
+        resolution: Option<usize>,
+        render: R,
+    ) -> Result<Self, SobolError>
+    where
+        T::IT: LossyFrom<P>,
+    {
+        let res = resolution
+            .filter(|res| *res <= T::MAX_RESOLUTION)
+            .unwrap_or(T::MAX_RESOLUTION);
+        let max_dims = params.max_dims();
+        if D > max_dims {
+            return Err(SobolError::MaxDim { dims: D, max_dims });
+        }
+        if let Some(render_dims) = render.support_dims()
+            && D != render_dims
+        {
+            return Err(SobolError::RenderDim {
+                dims: D,
+                render_dims,
+            });
+        }
+
+        let dirs = DirectionTable::<T>::init_direction_vals::<P, Param>(D, res, params);
+        // Transpose dir values for better cache locality
+        let dir_vals = (0..res)
+            .map(|i| array::from_fn(|dim| dirs[dim][i]))
+            .collect();
+        Ok(SobolN {
+            resolution: res,
+            dir_vals,
+            previous: [T::IT::zero(); D],
+            render,
+            count: T::IT::zero(),
+            max_len: T::IT::max_value() >> (T::IT::BITS - res),
+        })
+    }
+
+    /// Returns the zero-based index of the point the next call to `next()` will yield
+    #[inline]
+    pub fn position(&self) -> T::IT {
+        self.count
+    }
+
+    /// Repositions the sequence so that the next call to `next()` yields the point at the given
+    /// zero-based index. See `Sobol::seek`.
+    pub fn seek(&mut self, index: T::IT) {
+        let index = index.min(self.max_len);
+        self.previous = if index.is_zero() {
+            [T::IT::zero(); D]
+        } else {
+            self.values_at(index - T::IT::one())
+        };
+        self.count = index;
+    }
+
+    /// Returns the point at the given zero-based index (in Gray code order) without iterating
+    /// to it, or `None` if the index is beyond the end of the sequence. See `Sobol::point_at`.
+    pub fn point_at(&self, index: T::IT) -> Option<[T; D]> {
+        if index < self.max_len {
+            Some(self.render_point(&self.values_at(index)))
+        } else {
+            None
+        }
+    }
+
+    /// Computes the internal values of the point at `index` from its Gray code
+    fn values_at(&self, index: T::IT) -> [T::IT; D] {
+        let mut vals = [T::IT::zero(); D];
+        let mut mask = index ^ (index >> 1);
+        while !mask.is_zero() {
+            let dirs = &self.dir_vals[mask.trailing_zeros() as usize];
+            for (p, dir) in dirs.iter().zip(&mut vals) {
+                *dir = *p ^ *dir;
+            }
+            mask = mask & (mask - T::IT::one());
+        }
+        vals
+    }
+
+    #[inline]
+    fn render_point(&self, vals: &[T::IT; D]) -> [T; D] {
+        array::from_fn(|dim| self.render.render(dim, vals[dim]))
+    }
+}
+
+impl<T: SobolType, const D: usize, R: Render<T>> Iterator for SobolN<T, D, R> {
+    type Item = [T; D];
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.count < self.max_len {
+            if !self.count.is_zero() {
+                let a = self.count - T::IT::one();
+                let c = (a ^ T::IT::max_value()).trailing_zeros() as usize;
+                for (p, dir) in self.dir_vals[c].iter().zip(&mut self.previous) {
+                    *dir = *p ^ *dir;
+                }
+            }
+            self.count += T::IT::one();
+            Some(self.render_point(&self.previous))
+        } else {
+            None
+        }
+    }
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        match <T::IT as NumCast>::from(n) {
+            Some(n) if n < self.max_len - self.count => self.seek(self.count + n),
+            _ => self.seek(self.max_len),
+        }
+        self.next()
+    }
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match (self.max_len - self.count).to_usize() {
+            Some(len) => (len, Some(len)),
+            None => (usize::MAX, None),
+        }
+    }
+}
+
+/// Only implemented where the remaining length always fits a `usize`, see `ExactLenType`
+impl<T: SobolType, const D: usize, R: Render<T>> ExactSizeIterator for SobolN<T, D, R> where
+    T::IT: ExactLenType
+{
+}
+
+impl<T: SobolType, const D: usize, R: Render<T>> FusedIterator for SobolN<T, D, R> {}
@@ -0,0 +1,258 @@
+use crate::{
+    Render, SobolError, SobolType, UnitRender,
+    base_b::fixed_point,
+    scramble::{RandomSource, SplitMix64, resolution_mask},
+};
+use num_traits::ToPrimitive;
+use std::iter::FusedIterator;
+
+/// A Halton sequence generator: dimension `d` is the radical inverse of the point index in the
+/// `d`-th prime base. Since each base stratifies on its own, Halton points are balanced for
+/// sample sizes that are not powers of two. Digits may be permuted per dimension (generalized
+/// or scrambled Halton) to break up the correlation between dimensions of large, nearby bases.
+///
+/// Values are computed exactly as fixed-point values of the internal type of `T`, rounded down
+/// to the resolution of `T`, and rendered by the same `Render`s as `Sobol`.
+pub struct Halton<T: SobolType, R: Render<T> = UnitRender> {
+    pub dims: usize,
+    pub resolution: usize,
+    pub count: u64,
+    bases: Vec<u32>,
+    /// Per-dimension digit permutations, or empty for the plain radical inverse
+    perms: Vec<Vec<u32>>,
+    render: R,
+    mask: T::IT,
+}
+
+impl<T: SobolType, R: Render<T>> Clone for Halton<T, R> {
+    fn clone(&self) -> Self {
+        Halton {
+            dims: self.dims,
+            resolution: self.resolution,
+            count: self.count,
+            bases: self.bases.clone(),
+            perms: self.perms.clone(),
+            render: self.render.clone(),
+            mask: self.mask,
+        }
+    }
+}
+
+impl<T: SobolType> Halton<T, UnitRender>
+where
+    UnitRender: Render<T>,
+{
+    /// Constructs a new sequence
+    pub fn new(dims: usize) -> Self {
+        Self::new_with_resolution(dims, None, UnitRender)
+            .expect("UnitRender supports any number of dimensions")
+    }
+}
+
+impl<T: SobolType, R: Render<T>> Halton<T, R> {
+    /// Constructs a new sequence of given resolution, i.e. the number of leading bits of each
+    /// value that are computed, by default the maximum resolution of `T`
+    pub fn new_with_resolution(
+        dims: usize,
+        resolution: Option<usize>,
+        render: R,
+    ) -> Result<Self, SobolError> {
+        if let Some(render_dims) = render.support_dims()
+            && dims != render_dims
+        {
+            return Err(SobolError::RenderDim { dims, render_dims });
+        }
+        let res = resolution
+            .filter(|res| *res <= T::MAX_RESOLUTION)
+            .unwrap_or(T::MAX_RESOLUTION);
+        Ok(Halton {
+            dims,
+            resolution: res,
+            count: 0,
+            bases: primes(dims),
+            perms: Vec::new(),
+            render,
+            mask: resolution_mask(res),
+        })
+    }
+
+    /// The prime base of each dimension
+    #[inline]
+    pub fn bases(&self) -> &[u32] {
+        &self.bases
+    }
+
+    /// Permutes the digits of each dimension with Faure's deterministic permutations, which
+    /// spread the leading digits of large bases to counter their correlation
+    pub fn with_faure_permutations(mut self) -> Self {
+        self.perms = self.bases.iter().map(|b| faure_permutation(*b)).collect();
+        self
+    }
+
+    /// Permutes the digits of each dimension with a uniformly random permutation (fixing zero,
+    /// so that the infinite trailing zero digits of each index remain zero) drawn from the
+    /// given seed
+    pub fn with_random_permutations(self, seed: u64) -> Self {
+        self.with_random_permutations_from(&mut SplitMix64::new(seed))
+    }
+
+    /// Same as `with_random_permutations` but drawing the permutations from the given source
+    pub fn with_random_permutations_from(mut self, rng: &mut impl RandomSource) -> Self {
+        self.perms = self
+            .bases
+            .iter()
+            .map(|b| {
+                let mut perm: Vec<u32> = (0..*b).collect();
+                for i in (2..perm.len()).rev() {
+                    let j = 1 + ((rng.next_u64() as u128 * i as u128) >> 64) as usize;
+                    perm.swap(i, j);
+                }
+                perm
+            })
+            .collect();
+        self
+    }
+
+    /// Returns the point at the given zero-based index, or `None` if the index is beyond the
+    /// end of the sequence. Does not affect the position of the sequence.
+    pub fn point_at(&self, index: u64) -> Option<Vec<T>> {
+        if index < u64::MAX {
+            Some(
+                (0..self.dims)
+                    .map(|dim| self.render.render(dim, self.value_at(index, dim)))
+                    .collect(),
+            )
+        } else {
+            None
+        }
+    }
+
+    /// Same as `point_at` but writes the point into the given buffer. Returns `false` (leaving
+    /// the buffer untouched) if the index is beyond the end of the sequence.
+    ///
+    /// Panics if the length of the buffer differs from `dims`.
+    pub fn point_at_into(&self, index: u64, out: &mut [T]) -> bool {
+        assert_eq!(
+            out.len(),
+            self.dims,
+            "buffer length must match the number of sequence dimensions"
+        );
+        if index < u64::MAX {
+            for (dim, o) in out.iter_mut().enumerate() {
+                *o = self.render.render(dim, self.value_at(index, dim));
+            }
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Writes the next point into the given buffer rather than allocating a new `Vec` as
+    /// `next()` does. Returns `false` (leaving the buffer untouched) once the sequence is
+    /// exhausted.
+    ///
+    /// Panics if the length of the buffer differs from `dims`.
+    pub fn next_into(&mut self, out: &mut [T]) -> bool {
+        let written = self.point_at_into(self.count, out);
+        if written {
+            self.count += 1;
+        }
+        written
+    }
+
+    /// Moves the sequence to the given zero-based index
+    #[inline]
+    pub fn seek(&mut self, index: u64) {
+        self.count = index;
+    }
+
+    /// The (permuted) radical inverse of the index in the base of the given dimension, as a
+    /// fixed-point value rounded down to the resolution of the sequence
+    fn value_at(&self, mut index: u64, dim: usize) -> T::IT {
+        let base = self.bases[dim] as u128;
+        let perm = self.perms.get(dim);
+
+        // The index reversed about the radix point is `num / den`; with 32-bit bases and 64-bit
+        // indices both stay below 2^96, as `fixed_point` requires
+        let (mut num, mut den) = (0u128, 1u128);
+        while index > 0 {
+            let digit = (index % base as u64) as usize;
+            index /= base as u64;
+            num = num * base + perm.map_or(digit as u128, |p| p[digit] as u128);
+            den *= base;
+        }
+
+        fixed_point::<T::IT>(num, den) & self.mask
+    }
+}
+
+impl<T: SobolType, R: Render<T>> Iterator for Halton<T, R> {
+    type Item = Vec<T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let point = self.point_at(self.count)?;
+        self.count += 1;
+        Some(point)
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.count = self.count.saturating_add(n as u64);
+        self.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match (u64::MAX - self.count).to_usize() {
+            Some(len) => (len, Some(len)),
+            None => (usize::MAX, None),
+        }
+    }
+}
+
+/// Indices are 64-bit, so the remaining length only always fits a `usize` on 64-bit targets
+#[cfg(target_pointer_width = "64")]
+impl<T: SobolType, R: Render<T>> ExactSizeIterator for Halton<T, R> {}
+
+impl<T: SobolType, R: Render<T>> FusedIterator for Halton<T, R> {}
+
+/// The first `n` primes
+fn primes(n: usize) -> Vec<u32> {
+    let mut primes = Vec::with_capacity(n);
+    let mut candidate = 2u32;
+    while primes.len() < n {
+        if primes
+            .iter()
+            .take_while(|p| *p * *p <= candidate)
+            .all(|p| !candidate.is_multiple_of(*p))
+        {
+            primes.push(candidate);
+        }
+        candidate += 1;
+    }
+    primes
+}
+
+/// Faure's permutation of the digits of the given base, built recursively: for an even base
+/// `2c` the permutation for `c` is doubled into the even and then odd digits, and for an odd
+/// base `2c + 1` the permutation for `2c` is widened around a middle digit `c`.
+fn faure_permutation(base: u32) -> Vec<u32> {
+    match base {
+        0..=2 => (0..base).collect(),
+        _ if base.is_multiple_of(2) => {
+            let half = faure_permutation(base / 2);
+            let evens = half.iter().map(|d| 2 * d);
+            evens.chain(half.iter().map(|d| 2 * d + 1)).collect()
+        }
+        _ => {
+            let c = base / 2;
+            let mut perm: Vec<u32> = faure_permutation(base - 1)
+                .into_iter()
+                .map(|d| if d >= c { d + 1 } else { d })
+                .collect();
+            perm.insert(c as usize, c);
+            perm
+        }
+    }
+}
@@ -1,24 +1,82 @@
+mod base_b;
+mod fixed;
+mod halton;
+mod niederreiter;
+mod padded;
+#[cfg(feature = "rayon")]
+mod par;
 pub mod params;
+mod rqmc;
+mod scramble;
+mod sequence;
+mod stream;
+mod table;
 mod type_support;
+pub use base_b::BaseBSequence;
 use core::{
     fmt,
-    ops::{AddAssign, BitXorAssign},
+    ops::{AddAssign, BitXorAssign, Range},
 };
-use num_traits::{Bounded, One, PrimInt, Unsigned, Zero};
+pub use fixed::SobolN;
+pub use halton::Halton;
+use num_traits::{
+    Bounded, NumCast, One, PrimInt, ToPrimitive, Unsigned, WrappingAdd, WrappingMul, Zero,
+};
+pub use padded::PaddedSobol;
+#[cfg(feature = "rayon")]
+pub use par::ParSobol;
+use params::GeneratorMatrices;
+pub use rqmc::{Randomization, Rqmc, RqmcEstimate};
+#[cfg(feature = "rand_core")]
+pub use scramble::RngSource;
+use scramble::Scramble;
+pub use scramble::{RandomSource, SplitMix64};
+pub use sequence::{LowDiscrepancy, RandomAccess};
 pub use statrs;
 use statrs::distribution::Normal;
-use std::iter::repeat_n;
+use std::iter::{FusedIterator, repeat_n};
+use std::sync::Arc;
+pub use stream::StreamFactory;
+pub use table::DirectionTable;
 
-/// A low-discrepancy Sobol sequence generator
-#[derive(Clone)]
+/// A low-discrepancy Sobol sequence generator. Points are generated from the direction values
+/// (generator matrix columns) of a `DirectionTable`, by default those of Sobol's sequence, but
+/// any other base 2 digital sequence can be generated the same way from its own table, see
+/// `Sobol::from_matrices` and `Sobol::new_niederreiter`.
 pub struct Sobol<T: SobolType, R: Render<T> = UnitRender> {
     pub dims: usize,
     pub resolution: usize,
-    dir_vals: Vec<Vec<T::IT>>,
+    dir_vals: Arc<DirectionTable<T>>,
     previous: Vec<T::IT>,
     render: R,
+    scramble: Scramble<T::IT>,
+    ordering: PointOrder,
     pub count: T::IT,
     pub max_len: T::IT,
+    /// Exclusive upper bound of the indices still to be yielded
+    end: T::IT,
+    /// Internal values of the point at `end` when iterating from the back, computed on demand
+    back: Vec<T::IT>,
+}
+
+// Implemented by hand as the derive would needlessly require `T: Clone`. The other sequence
+// types and their wrappers follow suit, so they are cloneable under the same bounds.
+impl<T: SobolType, R: Render<T>> Clone for Sobol<T, R> {
+    fn clone(&self) -> Self {
+        Sobol {
+            dims: self.dims,
+            resolution: self.resolution,
+            dir_vals: self.dir_vals.clone(),
+            previous: self.previous.clone(),
+            render: self.render.clone(),
+            scramble: self.scramble.clone(),
+            ordering: self.ordering,
+            count: self.count,
+            max_len: self.max_len,
+            end: self.end,
+            back: self.back.clone(),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, thiserror::Error)]
@@ -29,6 +87,41 @@ pub enum SobolError {
     MaxDim { dims: usize, max_dims: usize },
     #[error("Render supports a {render_dims} dimensions, but Sobol was configured for {dims}.")]
     RenderDim { dims: usize, render_dims: usize },
+    #[error(
+        "Sobol sequence supports dimensions 1 to {max_dims}, but dimension {dim} was requested."
+    )]
+    InvalidDim { dim: usize, max_dims: usize },
+    #[error(
+        "Generator matrix of dimension {dim} has {columns} columns, but resolution {resolution} requires as many."
+    )]
+    MatrixSize {
+        dim: usize,
+        columns: usize,
+        resolution: usize,
+    },
+    #[error("Malformed generator matrices on line {line}.")]
+    MatrixFormat { line: usize },
+    #[error("Digital sequences require a base of at least 2, but base {base} was requested.")]
+    InvalidBase { base: u32 },
+    #[error(
+        "Generator matrix of dimension {dim} has {columns} columns, but base b sequences require {digits}."
+    )]
+    DigitColumns {
+        dim: usize,
+        columns: usize,
+        digits: usize,
+    },
+    #[error(
+        "Column {column} of the generator matrix of dimension {dim} has {rows} rows, but base b sequences require {digits}."
+    )]
+    DigitRows {
+        dim: usize,
+        column: usize,
+        rows: usize,
+        digits: usize,
+    },
+    #[error("Padded sequences require groups of at least one dimension, but got {group_dims}.")]
+    GroupDims { group_dims: usize },
 }
 
 impl<T: SobolType> Sobol<T, UnitRender>
@@ -48,6 +141,7 @@ impl<T: SobolType, R: Render<T>> Sobol<T, R> {
     /// computation of the sequence and by default is the size of the underlying type. This
     /// constructor is useful for reducing the number of cycles necessary to generate each point when the
     /// length of the sequence is not expected to approach it's theoretically maximum (2^res).
+    /// A new direction table is built on every call, see `DirectionTable::cached` to share one.
     pub fn new_with_resolution<P, Param: SobolParams<P>>(
         dims: usize,
         params: &Param,
@@ -57,35 +151,244 @@ impl<T: SobolType, R: Render<T>> Sobol<T, R> {
     where
         T::IT: LossyFrom<P>,
     {
-        let res = resolution
-            .filter(|res| *res <= T::MAX_RESOLUTION)
-            .unwrap_or(T::MAX_RESOLUTION);
-        let max_dims = params.max_dims();
-        if dims > params.max_dims() {
-            return Err(SobolError::MaxDim { dims, max_dims });
-        }
-        if let Some(render_dims) = render.support_dims() {
-            if dims != render_dims {
-                return Err(SobolError::RenderDim { dims, render_dims });
-            }
+        if let Some(render_dims) = render.support_dims()
+            && dims != render_dims
+        {
+            return Err(SobolError::RenderDim { dims, render_dims });
         }
+        let table = DirectionTable::new::<P, Param>(dims, params, resolution)?;
+        Self::from_table(Arc::new(table), render)
+    }
 
-        let dir_values = Self::init_direction_vals::<P, Param>(dims, res, params);
-        // Transpose dir values for better cache locality
-        let dir_values = (0..dir_values[0].len())
-            .map(|i| dir_values.iter().map(|inner| inner[i]).collect::<Vec<_>>())
-            .collect();
+    /// Constructs a new sequence from an explicit list of (one-based) parameter dimensions, e.g.
+    /// `[1, 2, 7, 40]` or `5..15`, rather than the first `dims` dimensions. The n-th component
+    /// of each point is drawn from the n-th dimension listed. See `DirectionTable::with_dims`.
+    pub fn new_with_dims<P, Param: SobolParams<P>>(
+        dims: impl IntoIterator<Item = usize>,
+        params: &Param,
+        resolution: Option<usize>,
+        render: R,
+    ) -> Result<Self, SobolError>
+    where
+        T::IT: LossyFrom<P>,
+    {
+        let table = DirectionTable::with_dims::<P, Param>(dims, params, resolution)?;
+        Self::from_table(Arc::new(table), render)
+    }
+
+    /// Constructs a new sequence from existing (possibly shared) direction values, e.g. those
+    /// returned by `DirectionTable::cached`. Only the cursor state is allocated.
+    pub fn from_table(table: Arc<DirectionTable<T>>, render: R) -> Result<Self, SobolError> {
+        let dims = table.dims();
+        let res = table.resolution();
+        if let Some(render_dims) = render.support_dims()
+            && dims != render_dims
+        {
+            return Err(SobolError::RenderDim { dims, render_dims });
+        }
+        let max_len = T::IT::max_value() >> (T::IT::BITS - res);
         Ok(Sobol {
             dims,
             resolution: res,
-            dir_vals: dir_values,
+            dir_vals: table,
             count: T::IT::zero(),
-            max_len: T::IT::max_value() >> (T::IT::BITS - res),
+            max_len,
             previous: Vec::with_capacity(dims),
             render,
+            scramble: Scramble::default(),
+            ordering: PointOrder::Gray,
+            end: max_len,
+            back: Vec::new(),
         })
     }
 
+    /// Constructs a digital sequence from explicit generator matrices over GF(2), see
+    /// `DirectionTable::from_matrices`
+    pub fn from_matrices(
+        matrices: &[Vec<T::IT>],
+        resolution: Option<usize>,
+        render: R,
+    ) -> Result<Self, SobolError> {
+        let table = DirectionTable::from_matrices(matrices, resolution)?;
+        Self::from_table(Arc::new(table), render)
+    }
+
+    /// Constructs a digital sequence from the first `dims` of the given published generator
+    /// matrices, see `DirectionTable::from_generator_matrices`
+    pub fn from_generator_matrices(
+        matrices: &GeneratorMatrices,
+        dims: usize,
+        resolution: Option<usize>,
+        render: R,
+    ) -> Result<Self, SobolError> {
+        let table = DirectionTable::from_generator_matrices(matrices, dims, resolution)?;
+        Self::from_table(Arc::new(table), render)
+    }
+
+    /// Constructs a base 2 Niederreiter sequence, see `DirectionTable::niederreiter`. It is
+    /// generated, randomized and rendered exactly like a Sobol sequence.
+    pub fn new_niederreiter(
+        dims: usize,
+        resolution: Option<usize>,
+        render: R,
+    ) -> Result<Self, SobolError> {
+        Self::from_table(
+            Arc::new(DirectionTable::niederreiter(dims, resolution)),
+            render,
+        )
+    }
+
+    /// The direction values this sequence is generated from
+    #[inline]
+    pub fn table(&self) -> &Arc<DirectionTable<T>> {
+        &self.dir_vals
+    }
+
+    /// Sets the order in which points are produced, rewinding the sequence to its first point.
+    /// Sequences are constructed in `PointOrder::Gray` order.
+    pub fn with_ordering(mut self, ordering: PointOrder) -> Self {
+        self.ordering = ordering;
+        self.count = T::IT::zero();
+        self.reset_cursor();
+        self
+    }
+
+    /// The order in which points are produced
+    #[inline]
+    pub fn ordering(&self) -> PointOrder {
+        self.ordering
+    }
+
+    /// Applies a random digital shift: the internal value of each dimension is XORed with a
+    /// random word drawn from the given seed before it is rendered. This yields unbiased
+    /// randomized QMC estimates while preserving the (t,m,s)-net structure of the sequence.
+    /// The shift is part of the generator state, so it is preserved by `clone()` and `seek`,
+    /// and a given seed yields the same points on every platform.
+    pub fn with_digital_shift(self, seed: u64) -> Self {
+        self.with_digital_shift_from(&mut SplitMix64::new(seed))
+    }
+
+    /// Same as `with_digital_shift` but drawing the shift from the given random source
+    pub fn with_digital_shift_from(mut self, rng: &mut impl RandomSource) -> Self {
+        self.scramble
+            .set_digital_shift(self.dims, self.resolution, rng);
+        self
+    }
+
+    /// Applies Matoušek's random linear matrix scrambling (see `DirectionTable::linear_scramble`)
+    /// with the given seed, replacing the sequence's direction values with a scrambled copy.
+    /// The per-point cost of generation is unchanged. Combined with `with_digital_shift` this
+    /// is the scrambling scheme used by e.g. SciPy's `qmc.Sobol(scramble=True)`. The position
+    /// of the sequence is preserved.
+    pub fn with_linear_scramble(self, seed: u64) -> Self {
+        self.with_linear_scramble_from(&mut SplitMix64::new(seed))
+    }
+
+    /// Same as `with_linear_scramble` but drawing the scrambling matrices from the given
+    /// random source
+    pub fn with_linear_scramble_from(mut self, rng: &mut impl RandomSource) -> Self {
+        self.dir_vals = Arc::new(self.dir_vals.linear_scramble_from(rng));
+        self.reset_cursor();
+        self
+    }
+
+    /// Applies Faure-Tezuka scrambling (see `DirectionTable::faure_tezuka_scramble`) with the
+    /// given seed: the digits of the point index are scrambled before they are mapped through
+    /// the direction values. Combined with `with_linear_scramble` this gives the full (left and
+    /// right) linear scramble. The position of the sequence is preserved.
+    pub fn with_faure_tezuka_scramble(self, seed: u64) -> Self {
+        self.with_faure_tezuka_scramble_from(&mut SplitMix64::new(seed))
+    }
+
+    /// Same as `with_faure_tezuka_scramble` but drawing the scrambling matrix from the given
+    /// random source
+    pub fn with_faure_tezuka_scramble_from(mut self, rng: &mut impl RandomSource) -> Self {
+        self.dir_vals = Arc::new(self.dir_vals.faure_tezuka_scramble_from(rng));
+        self.reset_cursor();
+        self
+    }
+
+    /// Rebuilds the cursor state at the current position after the direction values or their
+    /// ordering have changed
+    fn reset_cursor(&mut self) {
+        self.back.clear();
+        self.seek(self.count);
+    }
+
+    /// Applies Owen's nested uniform scrambling, with one seed per dimension derived from the
+    /// given seed. Each bit of a dimension's internal value is flipped according to a random
+    /// function of the bits above it, which improves the variance rate of estimates for smooth
+    /// integrands. The permutations are computed by hashing (see `with_owen_scramble_seeds`)
+    /// at render time, so no permutation trees are stored. Owen scrambling subsumes a digital
+    /// shift; if both are configured, the shift is applied after the scramble.
+    pub fn with_owen_scramble(self, seed: u64) -> Self {
+        self.with_owen_scramble_from(&mut SplitMix64::new(seed))
+    }
+
+    /// Same as `with_owen_scramble` but drawing the per-dimension seeds from the given random
+    /// source
+    pub fn with_owen_scramble_from(self, rng: &mut impl RandomSource) -> Self {
+        let seeds: Vec<_> = (0..self.dims).map(|_| rng.next_u64()).collect();
+        self.with_owen_scramble_seeds(seeds)
+    }
+
+    /// Same as `with_owen_scramble` but with an explicit seed for each dimension. Dimensions
+    /// beyond the number of seeds given are left unscrambled. Bits are scrambled with Burley's
+    /// hash-based variant of the Laine-Karras permutation applied to the bit-reversed value,
+    /// which works for every `SobolType` including 64-bit types at full resolution.
+    pub fn with_owen_scramble_seeds(mut self, seeds: impl IntoIterator<Item = u64>) -> Self {
+        self.scramble
+            .set_owen(seeds.into_iter().take(self.dims), self.resolution);
+        self
+    }
+
+    /// Applies the given versioned scrambling scheme with the given seed. Unlike the individual
+    /// randomization methods, whose defaults may evolve, the output of each scheme is frozen
+    /// (see `ScrambleScheme`).
+    pub fn with_scramble(self, scheme: ScrambleScheme, seed: u64) -> Self {
+        match scheme {
+            ScrambleScheme::V1 => self.owen_scramble_v1(seed),
+        }
+    }
+
+    /// `ScrambleScheme::V1`, spelled out step by step rather than routed through
+    /// `with_owen_scramble` so that changes to the latter cannot alter it
+    fn owen_scramble_v1(mut self, seed: u64) -> Self {
+        let mut master = SplitMix64::new(seed);
+        let words = (0..self.dims)
+            .map(|_| {
+                let mut rng = SplitMix64::new(master.next_u64());
+                if T::IT::BITS > 64 {
+                    let hi = T::IT::lossy_from(rng.next_u64());
+                    (hi << 64) | T::IT::lossy_from(rng.next_u64())
+                } else {
+                    T::IT::lossy_from(rng.next_u64())
+                }
+            })
+            .collect();
+        self.scramble.set_owen_words(words, self.resolution);
+        self
+    }
+
+    /// Applies a random rotation on the grid of the sequence's resolution, the discrete
+    /// counterpart of a Cranley-Patterson rotation: a shift drawn uniformly from the multiples of
+    /// `2^-resolution` with the given seed is added to each dimension modulo one. Rotated values
+    /// thus stay on the grid (and floats strictly inside `[0,1)`), but are only unbiased up to
+    /// that grid: each value is uniform over the grid points, whose mean falls short of one half
+    /// by `2^-(resolution + 1)`, which matters only at low resolutions. The addition is carried
+    /// out on internal values before rendering, so it propagates through any `Render` (e.g.
+    /// `GaussianRender` sees the rotated uniform). When combined with `with_digital_shift`, the
+    /// digital shift is applied first.
+    pub fn with_random_rotation(self, seed: u64) -> Self {
+        self.with_random_rotation_from(&mut SplitMix64::new(seed))
+    }
+
+    /// Same as `with_random_rotation` but drawing the rotation from the given random source
+    pub fn with_random_rotation_from(mut self, rng: &mut impl RandomSource) -> Self {
+        self.scramble.set_rotation(self.dims, self.resolution, rng);
+        self
+    }
+
     /// Initializes per-dimension direction values given sequence parameters
     pub fn init_direction_vals<P, Param: SobolParams<P>>(
         dims: usize,
@@ -95,44 +398,7 @@ impl<T: SobolType, R: Render<T>> Sobol<T, R> {
     where
         T::IT: LossyFrom<P>,
     {
-        let bits = T::IT::BITS;
-
-        (1..=dims)
-            .map(|dim| match dim {
-                1 => (1..=resolution)
-                    .map(|i| T::IT::one() << (bits - i))
-                    .collect(),
-                _ => {
-                    // Import the parameters needed to prepare this dimension's direction vector
-                    let p = params.get_dim(dim);
-                    let s = if resolution >= p.s() {
-                        p.s()
-                    } else {
-                        resolution
-                    };
-
-                    // Shift initial directions
-                    let mut dirs: Vec<T::IT> = vec![T::IT::zero(); resolution];
-                    for i in 1..=s {
-                        let m = T::IT::lossy_from(p.m(i - 1));
-                        dirs[i - 1] = m << (bits - i);
-                    }
-
-                    // Compute remaining directions
-                    for i in s + 1..=resolution {
-                        dirs[i - 1] = dirs[i - s - 1] ^ (dirs[i - s - 1] >> s);
-
-                        for k in 1..s {
-                            let a = T::IT::lossy_from(p.coefficient(s - k - 1));
-                            let dir = dirs[i - k - 1];
-                            dirs[i - 1] ^= a * dir;
-                        }
-                    }
-
-                    dirs
-                }
-            })
-            .collect()
+        DirectionTable::<T>::init_direction_vals(dims, resolution, params)
     }
 
     /// Returns zero-based index of the rightmost binary zero. Used for the Gray code optimization
@@ -147,20 +413,297 @@ impl<T: SobolType, R: Render<T>> Sobol<T, R> {
         if self.previous.is_empty() {
             self.previous.extend(repeat_n(T::IT::zero(), self.dims));
         } else {
-            let a = self.count - T::IT::one();
-            let c = Self::rightmost_zero(a);
-            for (p, dir) in self.dir_vals[c].iter().zip(&mut self.previous) {
-                *dir = *p ^ *dir;
+            for c in self.step_dirs(self.count) {
+                for (p, dir) in self.dir_vals.row(c).iter().zip(&mut self.previous) {
+                    *dir = *p ^ *dir;
+                }
             }
         }
         self.count += T::IT::one();
     }
+
+    /// Returns the rows of direction values to combine with the point preceding `index` to
+    /// arrive at the point at (non-zero) `index`. In Gray code order this is a single row,
+    /// while in natural order it is every row up to and including it.
+    #[inline]
+    fn step_dirs(&self, index: T::IT) -> Range<usize> {
+        let c = Self::rightmost_zero(index - T::IT::one());
+        match self.ordering {
+            PointOrder::Gray => c..c + 1,
+            PointOrder::Natural => 0..c + 1,
+        }
+    }
+
+    /// Returns the mask of direction values which are combined for the point at `index`
+    #[inline]
+    fn index_mask(&self, index: T::IT) -> T::IT {
+        match self.ordering {
+            PointOrder::Gray => index ^ (index >> 1),
+            PointOrder::Natural => index,
+        }
+    }
+
+    /// Returns the zero-based index of the point the next call to `next()` will yield
+    #[inline]
+    pub fn position(&self) -> T::IT {
+        self.count
+    }
+
+    /// Repositions the sequence so that the next call to `next()` yields the point at the given
+    /// zero-based index. Rather than stepping through every intermediate point, the state is
+    /// rebuilt directly from the bits of the preceding index in O(resolution × dims). Seeking
+    /// beyond the end of the sequence (or of its `range`) exhausts it.
+    pub fn seek(&mut self, index: T::IT) {
+        let index = index.min(self.end);
+        let mut previous = core::mem::take(&mut self.previous);
+        previous.clear();
+        if !index.is_zero() {
+            self.combine_dirs(self.index_mask(index - T::IT::one()), &mut previous);
+        }
+        self.previous = previous;
+        self.count = index;
+    }
+
+    /// Restricts the sequence to the points whose zero-based indices lie within the given range
+    /// (clamped to the end of the sequence) and positions it at the start of the range. The
+    /// bounded sequence can then be walked from either end.
+    pub fn range(mut self, range: Range<T::IT>) -> Self {
+        self.end = range.end.min(self.max_len);
+        self.back.clear();
+        self.seek(range.start);
+        self
+    }
+
+    /// Splits the remaining points of the sequence at the given zero-based index, returning
+    /// independent generators for the points before and from that index. Iterating the first
+    /// followed by the second reproduces the output of the original generator exactly.
+    pub fn split_at(self, index: T::IT) -> (Self, Self) {
+        let index = index.max(self.count).min(self.end);
+        let tail = self.clone().range(index..self.end);
+        let mut head = self;
+        head.end = index;
+        head.back.clear();
+        (head, tail)
+    }
+
+    /// Splits the remaining points of the sequence into `n` independent generators covering
+    /// contiguous, non-overlapping index ranges of (nearly) equal length, e.g. one per worker.
+    /// Iterating them in order reproduces the output of the original generator exactly.
+    ///
+    /// Panics if `n` is zero.
+    pub fn chunks(self, n: usize) -> Vec<Self> {
+        assert!(n > 0, "number of chunks must be non-zero");
+        // Lengths are computed in 128 bits since `n` need not fit the internal type
+        let start = self.count.to_u128().unwrap();
+        let total = self.end.to_u128().unwrap() - start;
+        let (len, rem) = (total / n as u128, total % n as u128);
+        let mut rest = self;
+        let mut chunks = Vec::with_capacity(n);
+        for i in 1..n as u128 {
+            let index = start + len * i + i.min(rem);
+            let (head, tail) = rest.split_at(<T::IT as NumCast>::from(index).unwrap());
+            chunks.push(head);
+            rest = tail;
+        }
+        chunks.push(rest);
+        chunks
+    }
+
+    /// Replaces `vals` with the internal values obtained by combining the direction values
+    /// selected by the set bits of `mask`, one row of the direction table at a time
+    fn combine_dirs(&self, mut mask: T::IT, vals: &mut Vec<T::IT>) {
+        vals.clear();
+        vals.extend(repeat_n(T::IT::zero(), self.dims));
+        while !mask.is_zero() {
+            let dirs = self.dir_vals.row(mask.trailing_zeros() as usize);
+            for (p, dir) in dirs.iter().zip(vals.iter_mut()) {
+                *dir = *p ^ *dir;
+            }
+            mask = mask & (mask - T::IT::one());
+        }
+    }
+
     #[inline]
     pub fn render_next(&self) -> impl Iterator<Item = T> {
         self.previous
             .iter()
             .enumerate()
-            .map(|(dim, val)| self.render.render(dim, *val))
+            .map(|(dim, val)| self.render_value(dim, *val))
+    }
+
+    /// Advances the sequence and writes the next point into the given buffer rather than
+    /// allocating a new `Vec` as `next()` does. Returns `false` (leaving the buffer untouched)
+    /// once the sequence is exhausted.
+    ///
+    /// Panics if the length of the buffer differs from `dims`.
+    #[inline]
+    pub fn next_into(&mut self, out: &mut [T]) -> bool {
+        self.check_buf_len(out.len());
+        if self.count < self.end {
+            self.update();
+            for (o, val) in out.iter_mut().zip(self.render_next()) {
+                *o = val;
+            }
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Writes the next `n` points into a single contiguous buffer of length `n * dims` using the
+    /// given `Layout`, and advances the sequence past them. Returns the number of points
+    /// written, which is less than `n` only when the sequence is exhausted. In that case, with
+    /// `Layout::DimensionMajor` each column keeps its stride of `n` and only its leading
+    /// entries are written.
+    ///
+    /// Panics if the length of the buffer differs from `n * dims`.
+    pub fn fill_block(&mut self, n: usize, out: &mut [T], layout: Layout) -> usize {
+        assert_eq!(
+            out.len(),
+            n * self.dims,
+            "buffer length must match the number of points times the number of dimensions"
+        );
+        let remaining = (self.end - self.count).to_usize().unwrap_or(usize::MAX);
+        let len = n.min(remaining);
+        match layout {
+            Layout::PointMajor => {
+                // Sliced by hand since chunks of zero-dimensional points would be empty
+                for i in 0..len {
+                    self.update();
+                    let point = &mut out[i * self.dims..(i + 1) * self.dims];
+                    for (o, val) in point.iter_mut().zip(self.render_next()) {
+                        *o = val;
+                    }
+                }
+            }
+            Layout::DimensionMajor => {
+                if len == 0 {
+                    return 0;
+                }
+                let start = self.count;
+                if self.previous.is_empty() {
+                    self.previous.extend(repeat_n(T::IT::zero(), self.dims));
+                }
+                // Walk one dimension at a time so each column of output is written contiguously
+                for (dim, column) in out.chunks_exact_mut(n).enumerate() {
+                    let mut val = self.previous[dim];
+                    let mut index = start;
+                    for o in &mut column[..len] {
+                        if !index.is_zero() {
+                            for c in self.step_dirs(index) {
+                                val ^= self.dir_vals.get(c, dim);
+                            }
+                        }
+                        *o = self.render_value(dim, val);
+                        index += T::IT::one();
+                    }
+                    self.previous[dim] = val;
+                }
+                self.count = start + <T::IT as NumCast>::from(len).unwrap();
+            }
+        }
+        len
+    }
+
+    /// Returns the point at the given zero-based index without iterating to it. Points are
+    /// returned in the sequence's `PointOrder` (Gray code order by default), i.e. `point_at(i)` is
+    /// the same point the `i`-th call to `next()` yields on a fresh sequence. Returns `None` if
+    /// the index is beyond the end of the sequence.
+    pub fn point_at(&self, index: T::IT) -> Option<Vec<T>> {
+        if index < self.max_len {
+            let mask = self.index_mask(index);
+            Some(
+                (0..self.dims)
+                    .map(|dim| self.render_at(mask, dim))
+                    .collect(),
+            )
+        } else {
+            None
+        }
+    }
+
+    /// Same as `point_at` but writes the point into the given buffer. Returns `false` (leaving
+    /// the buffer untouched) if the index is beyond the end of the sequence.
+    ///
+    /// Panics if the length of the buffer differs from `dims`.
+    pub fn point_at_into(&self, index: T::IT, out: &mut [T]) -> bool {
+        self.check_buf_len(out.len());
+        if index < self.max_len {
+            let mask = self.index_mask(index);
+            self.render_at_into(mask, out);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Returns the point at the given zero-based index in natural (binary) order regardless of
+    /// the sequence's `PointOrder`, i.e. the point obtained by applying the direction values
+    /// selected by the bits of `index` itself rather than those of its Gray code. Both orders
+    /// visit the same set of points over the first `2^m` indices, but not within other
+    /// prefixes. Returns `None` if the index is beyond the end of the sequence.
+    pub fn point_at_natural(&self, index: T::IT) -> Option<Vec<T>> {
+        if index < self.max_len {
+            Some(
+                (0..self.dims)
+                    .map(|dim| self.render_at(index, dim))
+                    .collect(),
+            )
+        } else {
+            None
+        }
+    }
+
+    /// Same as `point_at_natural` but writes the point into the given buffer. Returns `false`
+    /// (leaving the buffer untouched) if the index is beyond the end of the sequence.
+    ///
+    /// Panics if the length of the buffer differs from `dims`.
+    pub fn point_at_natural_into(&self, index: T::IT, out: &mut [T]) -> bool {
+        self.check_buf_len(out.len());
+        if index < self.max_len {
+            self.render_at_into(index, out);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Computes the internal value for a single dimension by combining the direction values
+    /// selected by the set bits of `mask`
+    #[inline]
+    fn value_at(&self, mut mask: T::IT, dim: usize) -> T::IT {
+        let mut val = T::IT::zero();
+        while !mask.is_zero() {
+            val ^= self.dir_vals.get(mask.trailing_zeros() as usize, dim);
+            mask = mask & (mask - T::IT::one());
+        }
+        val
+    }
+
+    #[inline]
+    fn render_at(&self, mask: T::IT, dim: usize) -> T {
+        self.render_value(dim, self.value_at(mask, dim))
+    }
+
+    /// Randomizes (if configured) and renders the internal value of a single dimension
+    #[inline]
+    fn render_value(&self, dim: usize, val: T::IT) -> T {
+        self.render.render(dim, self.scramble.apply(dim, val))
+    }
+
+    #[inline]
+    fn render_at_into(&self, mask: T::IT, out: &mut [T]) {
+        for (dim, o) in out.iter_mut().enumerate() {
+            *o = self.render_at(mask, dim);
+        }
+    }
+
+    #[inline]
+    pub(crate) fn check_buf_len(&self, len: usize) {
+        assert_eq!(
+            len, self.dims,
+            "buffer length must match the number of sequence dimensions"
+        );
     }
 }
 
@@ -168,7 +711,7 @@ impl<T: SobolType, R: Render<T>> Iterator for Sobol<T, R> {
     type Item = Vec<T>;
     #[inline]
     fn next(&mut self) -> Option<Self::Item> {
-        if self.count < self.max_len {
+        if self.count < self.end {
             self.update();
             Some(self.render_next().collect())
         } else {
@@ -177,15 +720,110 @@ impl<T: SobolType, R: Render<T>> Iterator for Sobol<T, R> {
     }
     #[inline]
     fn nth(&mut self, n: usize) -> Option<Self::Item> {
-        for _ in 0..n {
-            if self.count < self.max_len {
-                self.update();
-            } else {
-                break;
-            }
+        match <T::IT as NumCast>::from(n) {
+            Some(n) if n < self.end - self.count => self.seek(self.count + n),
+            _ => self.seek(self.end),
         }
         self.next()
     }
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match (self.end - self.count).to_usize() {
+            Some(len) => (len, Some(len)),
+            None => (usize::MAX, None),
+        }
+    }
+}
+
+/// Only implemented where the remaining length always fits a `usize`, see `ExactLenType`.
+/// Other sequences still report their exact length through `size_hint` whenever it fits.
+impl<T: SobolType, R: Render<T>> ExactSizeIterator for Sobol<T, R> where T::IT: ExactLenType {}
+
+impl<T: SobolType, R: Render<T>> FusedIterator for Sobol<T, R> {}
+
+/// Walks the sequence backwards from its end (or the end of its `range`). Since each step of
+/// the recurrence is its own inverse, stepping back costs the same as stepping forward.
+impl<T: SobolType, R: Render<T>> DoubleEndedIterator for Sobol<T, R> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.count < self.end {
+            if self.back.is_empty() {
+                let mut back = core::mem::take(&mut self.back);
+                self.combine_dirs(self.index_mask(self.end), &mut back);
+                self.back = back;
+            }
+            for c in self.step_dirs(self.end) {
+                for (p, dir) in self.dir_vals.row(c).iter().zip(&mut self.back) {
+                    *dir = *p ^ *dir;
+                }
+            }
+            self.end = self.end - T::IT::one();
+            Some(
+                self.back
+                    .iter()
+                    .enumerate()
+                    .map(|(dim, val)| self.render_value(dim, *val))
+                    .collect(),
+            )
+        } else {
+            None
+        }
+    }
+    #[inline]
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        match <T::IT as NumCast>::from(n) {
+            Some(n) if n < self.end - self.count => self.end = self.end - n,
+            _ => self.end = self.count,
+        }
+        self.back.clear();
+        self.next_back()
+    }
+}
+
+/// The order in which a `Sobol` sequence produces its points
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PointOrder {
+    /// The Antonov-Saleev Gray code order, which takes a single XOR per dimension to advance
+    #[default]
+    Gray,
+    /// Natural binary order of the point index, as used by some published reference tables
+    Natural,
+}
+
+/// Frozen specifications of the seeded randomization applied by `Sobol::with_scramble`. A given
+/// scheme, seed, set of parameters, dimensions and resolution always yields bit-identical
+/// points, across crate versions, targets and endianness, so a stored seed is enough to
+/// regenerate a sample set. Changes to the randomization are introduced as new variants rather
+/// than by altering existing ones; golden vectors in `tests/data/scramble_v1.tsv.gz` lock
+/// down each scheme.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ScrambleScheme {
+    /// Owen nested uniform scrambling, identical to `with_owen_scramble` as of its
+    /// introduction but frozen independently of it:
+    ///
+    /// 1. A SplitMix64 generator (`state += 0x9e3779b97f4a7c15`, outputs mixed with
+    ///    `0xbf58476d1ce4e5b9` and `0x94d049bb133111eb`) is seeded with `seed` and one 64-bit
+    ///    output is drawn per dimension, in dimension order.
+    /// 2. Each of these seeds the first output(s) of a fresh SplitMix64 generator, giving a
+    ///    word of the internal type (for 128-bit types the first output is the upper half).
+    /// 3. Each internal value is bit-reversed, mixed with that word by the Laine-Karras hash
+    ///    `x ^= x * 0x3d20adea; x += w; x *= (w >> BITS / 2) | 1; x ^= x * 0x05526c56;
+    ///    x ^= x * 0x53a22864` (wrapping arithmetic, constants truncated to the type), bit
+    ///    reversed again and masked to the top `resolution` bits before it is rendered.
+    #[default]
+    V1,
+}
+
+/// Memory layout of a block of points written by `Sobol::fill_block`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Layout {
+    /// Row-major: the components of each point are contiguous, i.e. component `d` of point `i`
+    /// is found at `i * dims + d`
+    PointMajor,
+    /// Column-major: the values of each dimension are contiguous, i.e. component `d` of point
+    /// `i` is found at `d * n + i`
+    DimensionMajor,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -217,10 +855,29 @@ pub trait SobolType: Sized + fmt::Display {
 }
 
 /// Sequences are computed internally using unsigned types with the following capabilities
-pub trait InternalType: PrimInt + Unsigned + One + Zero + AddAssign + BitXorAssign + Copy {
+pub trait InternalType:
+    PrimInt
+    + Unsigned
+    + One
+    + Zero
+    + AddAssign
+    + BitXorAssign
+    + WrappingAdd
+    + WrappingMul
+    + Copy
+    + LossyFrom<u64>
+    + Send
+    + Sync
+    + 'static
+{
     const BITS: usize;
 }
 
+/// Internal types whose sequence lengths always fit a `usize`, so that sequences computed with
+/// them implement `ExactSizeIterator`: types of up to 32 bits, and 64-bit types on 64-bit
+/// targets. Sequences of wider types may be longer than `usize::MAX`.
+pub trait ExactLenType: InternalType {}
+
 /// Primitive polynomial parameters and initial direction values for all sequence dimensions
 pub trait SobolParams<P> {
     type Dimension: ParamDimension<P>;
@@ -229,6 +886,15 @@ pub trait SobolParams<P> {
 
     /// Maximum number of dimensions supported by this instance
     fn max_dims(&self) -> usize;
+
+    /// Identifies this instance's parameter values so that direction tables built from them
+    /// can be shared through `DirectionTable::cached`. Instances with equal keys and equal
+    /// `max_dims` must yield equal parameters. The key must not depend on the addresses of
+    /// constants, which may be duplicated, though those of statics are fine. By default tables
+    /// are not cached.
+    fn cache_key(&self) -> Option<u64> {
+        None
+    }
 }
 
 /// Primitive polynomial parameters and initial direction values for a single dimension
@@ -0,0 +1,104 @@
+use crate::InternalType;
+
+/// A polynomial over GF(2) as a little-endian vector of coefficient bits
+type Poly = Vec<u64>;
+
+/// Generator matrices of the base 2 Niederreiter sequence for the first `dims` dimensions, as
+/// `resolution` columns per dimension. Dimension `i` uses the `i`-th monic irreducible
+/// polynomial `p` (of degree `e`), and row `j = q e + k` of its matrix holds the coefficients
+/// of the Laurent series `x^(e - k - 1) / p(x)^(q + 1) = sum_r c_jr x^(-r - 1)`, as in Bratley,
+/// Fox and Niederreiter (1992).
+pub(crate) fn generator_matrices<IT: InternalType>(dims: usize, resolution: usize) -> Vec<Vec<IT>> {
+    irreducible_polynomials(dims)
+        .into_iter()
+        .map(|p| {
+            let e = degree(p);
+            let mut cols = vec![IT::zero(); resolution];
+            let mut power: Poly = vec![1];
+            for j in 0..resolution {
+                let (q, k) = (j / e, j % e);
+                if k == 0 {
+                    power = multiply(&power, p);
+                }
+                let d = q * e + e;
+
+                // Long division of `x^(e - k - 1)` by `p^(q + 1)`, one quotient digit at a time
+                let mut rem: Poly = vec![0; d / 64 + 1];
+                flip(&mut rem, e - k - 1);
+                for col in cols.iter_mut() {
+                    shift_left(&mut rem);
+                    if bit(&rem, d) {
+                        for (r, w) in rem.iter_mut().zip(&power) {
+                            *r ^= *w;
+                        }
+                        *col = *col | (IT::one() << (IT::BITS - 1 - j));
+                    }
+                }
+            }
+            cols
+        })
+        .collect()
+}
+
+/// The first `n` monic irreducible polynomials over GF(2), ordered by degree and then by value
+/// (`x`, `x + 1`, `x^2 + x + 1`, `x^3 + x + 1`, ...), as bit patterns
+pub(crate) fn irreducible_polynomials(n: usize) -> Vec<u64> {
+    (2u64..)
+        .filter(|f| {
+            let half = degree(*f) / 2;
+            (2..1 << (half + 1)).all(|g| remainder(*f, g) != 0)
+        })
+        .take(n)
+        .collect()
+}
+
+#[inline]
+fn degree(p: u64) -> usize {
+    63 - p.leading_zeros() as usize
+}
+
+/// The remainder of `f` divided by `g` over GF(2)
+fn remainder(mut f: u64, g: u64) -> u64 {
+    while f != 0 && degree(f) >= degree(g) {
+        f ^= g << (degree(f) - degree(g));
+    }
+    f
+}
+
+/// The product of a polynomial by a small polynomial over GF(2)
+fn multiply(a: &[u64], p: u64) -> Poly {
+    let mut out = vec![0; a.len() + 1];
+    for s in (0..64).filter(|s| (p >> s) & 1 == 1) {
+        for i in (0..a.len() * 64).filter(|i| bit(a, *i)) {
+            flip(&mut out, i + s);
+        }
+    }
+    out
+}
+
+#[inline]
+fn bit(p: &[u64], i: usize) -> bool {
+    p.get(i / 64).is_some_and(|w| (w >> (i % 64)) & 1 == 1)
+}
+
+#[inline]
+fn flip(p: &mut Poly, i: usize) {
+    if p.len() <= i / 64 {
+        p.resize(i / 64 + 1, 0);
+    }
+    p[i / 64] ^= 1 << (i % 64);
+}
+
+/// Multiplies a polynomial by `x`, growing it if needed
+#[inline]
+fn shift_left(p: &mut Poly) {
+    if p.last().is_some_and(|w| w >> 63 == 1) {
+        p.push(0);
+    }
+    let mut carry = 0;
+    for w in p.iter_mut() {
+        let next = *w >> 63;
+        *w = (*w << 1) | carry;
+        carry = next;
+    }
+}
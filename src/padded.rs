@@ -0,0 +1,213 @@
+use crate::{
+    DirectionTable, ExactLenType, InternalType, LossyFrom, Render, SobolError, SobolParams,
+    SobolType, UnitRender,
+    scramble::{RandomSource, SplitMix64, owen_scramble, resolution_mask},
+};
+use num_traits::{Bounded, NumCast, One, PrimInt, ToPrimitive, Zero};
+use std::iter::FusedIterator;
+use std::sync::Arc;
+
+/// A sequence of any number of dimensions built from only the first few dimensions of a Sobol
+/// sequence, as popularized for path tracing by Burley's "Practical Hash-based Owen Scrambling".
+/// Dimensions are grouped by the width of the underlying table (e.g. pairs for a two-dimensional
+/// table); each group draws its points from the table at an Owen-scrambled point index, with a
+/// different seed per group, and each dimension's value is Owen-scrambled in turn. Every group
+/// is thus a well distributed, independently randomized low-dimensional Sobol sequence, which
+/// avoids the poor high-dimensional projections of the parameters at large dimensions.
+pub struct PaddedSobol<T: SobolType, R: Render<T> = UnitRender> {
+    pub dims: usize,
+    pub resolution: usize,
+    pub count: T::IT,
+    pub max_len: T::IT,
+    dir_vals: Arc<DirectionTable<T>>,
+    render: R,
+    index_seeds: Vec<T::IT>,
+    value_seeds: Vec<T::IT>,
+}
+
+impl<T: SobolType, R: Render<T>> Clone for PaddedSobol<T, R> {
+    fn clone(&self) -> Self {
+        PaddedSobol {
+            dims: self.dims,
+            resolution: self.resolution,
+            count: self.count,
+            max_len: self.max_len,
+            dir_vals: self.dir_vals.clone(),
+            render: self.render.clone(),
+            index_seeds: self.index_seeds.clone(),
+            value_seeds: self.value_seeds.clone(),
+        }
+    }
+}
+
+impl<T: SobolType> PaddedSobol<T, UnitRender>
+where
+    UnitRender: Render<T>,
+{
+    /// Constructs a new sequence of `dims` dimensions padded from groups of `group_dims`
+    /// Sobol dimensions, randomized with the given seed
+    pub fn new<P, Param: SobolParams<P>>(
+        dims: usize,
+        group_dims: usize,
+        params: &Param,
+        seed: u64,
+    ) -> Result<Self, SobolError>
+    where
+        T::IT: LossyFrom<P>,
+    {
+        let table = DirectionTable::new::<P, Param>(group_dims, params, None)?;
+        Self::from_table(Arc::new(table), dims, seed, UnitRender)
+    }
+}
+
+impl<T: SobolType, R: Render<T>> PaddedSobol<T, R> {
+    /// Constructs a new sequence of `dims` dimensions from existing (possibly shared) direction
+    /// values, whose dimensions form the groups the sequence is padded from
+    pub fn from_table(
+        table: Arc<DirectionTable<T>>,
+        dims: usize,
+        seed: u64,
+        render: R,
+    ) -> Result<Self, SobolError> {
+        Self::from_table_from(table, dims, &mut SplitMix64::new(seed), render)
+    }
+
+    /// Same as `from_table` but drawing the scrambling seeds from the given random source
+    pub fn from_table_from(
+        table: Arc<DirectionTable<T>>,
+        dims: usize,
+        rng: &mut impl RandomSource,
+        render: R,
+    ) -> Result<Self, SobolError> {
+        if let Some(render_dims) = render.support_dims()
+            && dims != render_dims
+        {
+            return Err(SobolError::RenderDim { dims, render_dims });
+        }
+        let group_dims = table.dims();
+        if group_dims == 0 {
+            return Err(SobolError::GroupDims { group_dims });
+        }
+        let res = table.resolution();
+        let index_seeds = (0..dims.div_ceil(group_dims))
+            .map(|_| rng.next_word())
+            .collect();
+        let value_seeds = (0..dims).map(|_| rng.next_word()).collect();
+        Ok(PaddedSobol {
+            dims,
+            resolution: res,
+            count: T::IT::zero(),
+            max_len: T::IT::max_value() >> (T::IT::BITS - res),
+            dir_vals: table,
+            render,
+            index_seeds,
+            value_seeds,
+        })
+    }
+
+    /// The direction values the groups of this sequence are drawn from
+    #[inline]
+    pub fn table(&self) -> &Arc<DirectionTable<T>> {
+        &self.dir_vals
+    }
+
+    /// The number of dimensions in each group
+    #[inline]
+    pub fn group_dims(&self) -> usize {
+        self.dir_vals.dims()
+    }
+
+    /// Returns the point at the given zero-based index, or `None` if the index is beyond the
+    /// end of the sequence. Does not affect the position of the sequence.
+    pub fn point_at(&self, index: T::IT) -> Option<Vec<T>> {
+        if index < self.max_len {
+            Some(
+                (0..self.dims)
+                    .map(|dim| self.render_at(index, dim))
+                    .collect(),
+            )
+        } else {
+            None
+        }
+    }
+
+    /// Same as `point_at` but writes the point into the given buffer. Returns `false` (leaving
+    /// the buffer untouched) if the index is beyond the end of the sequence.
+    ///
+    /// Panics if the length of the buffer differs from `dims`.
+    pub fn point_at_into(&self, index: T::IT, out: &mut [T]) -> bool {
+        assert_eq!(
+            out.len(),
+            self.dims,
+            "buffer length must match the number of sequence dimensions"
+        );
+        if index < self.max_len {
+            for (dim, o) in out.iter_mut().enumerate() {
+                *o = self.render_at(index, dim);
+            }
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Moves the sequence to the given zero-based index, clamped to the end of the sequence
+    #[inline]
+    pub fn seek(&mut self, index: T::IT) {
+        self.count = index.min(self.max_len);
+    }
+
+    /// Computes and renders a single dimension of the point at the given index
+    fn render_at(&self, index: T::IT, dim: usize) -> T {
+        let group_dims = self.group_dims();
+        let index_mask = self.max_len;
+        let mut shuffled = owen_scramble(index, self.index_seeds[dim / group_dims]) & index_mask;
+
+        let mut val = T::IT::zero();
+        let sub_dim = dim % group_dims;
+        while !shuffled.is_zero() {
+            val ^= self
+                .dir_vals
+                .get(shuffled.trailing_zeros() as usize, sub_dim);
+            shuffled = shuffled & (shuffled - T::IT::one());
+        }
+        let val = owen_scramble(val, self.value_seeds[dim]) & resolution_mask(self.resolution);
+        self.render.render(dim, val)
+    }
+}
+
+impl<T: SobolType, R: Render<T>> Iterator for PaddedSobol<T, R> {
+    type Item = Vec<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let point = self.point_at(self.count)?;
+        self.count += T::IT::one();
+        Some(point)
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        let remaining = self.max_len - self.count;
+        match <T::IT as NumCast>::from(n) {
+            Some(n) if n < remaining => {
+                self.count += n;
+                self.next()
+            }
+            _ => {
+                self.count = self.max_len;
+                None
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match (self.max_len - self.count).to_usize() {
+            Some(n) => (n, Some(n)),
+            None => (usize::MAX, None),
+        }
+    }
+}
+
+/// Only implemented where the remaining length always fits a `usize`, see `ExactLenType`
+impl<T: SobolType, R: Render<T>> ExactSizeIterator for PaddedSobol<T, R> where T::IT: ExactLenType {}
+
+impl<T: SobolType, R: Render<T>> FusedIterator for PaddedSobol<T, R> {}
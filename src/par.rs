@@ -0,0 +1,148 @@
+use crate::{ExactLenType, Render, Sobol, SobolType};
+use num_traits::{NumCast, One};
+use rayon::iter::{
+    IndexedParallelIterator, IntoParallelIterator, ParallelIterator,
+    plumbing::{
+        Consumer, Folder, Producer, ProducerCallback, UnindexedConsumer, UnindexedProducer, bridge,
+        bridge_producer_consumer, bridge_unindexed,
+    },
+};
+
+/// Parallel iterator over the remaining points of a `Sobol` sequence. Work is divided by
+/// splitting the index range of the sequence, so each worker jumps directly to its first point.
+///
+/// Sequences of any length can be iterated in parallel, but the indexed operations of
+/// `IndexedParallelIterator` (e.g. `zip` or `enumerate`) are only available where the remaining
+/// length always fits a `usize`, see `ExactLenType`.
+pub struct ParSobol<T: SobolType, R: Render<T>>(Sobol<T, R>);
+
+impl<T: SobolType, R: Render<T>> Clone for ParSobol<T, R> {
+    fn clone(&self) -> Self {
+        ParSobol(self.0.clone())
+    }
+}
+
+impl<T, R> IntoParallelIterator for Sobol<T, R>
+where
+    T: SobolType + Send,
+    R: Render<T> + Send,
+{
+    type Iter = ParSobol<T, R>;
+    type Item = Vec<T>;
+    fn into_par_iter(self) -> Self::Iter {
+        ParSobol(self)
+    }
+}
+
+impl<T, R> IntoParallelIterator for &Sobol<T, R>
+where
+    T: SobolType + Send,
+    R: Render<T> + Send,
+{
+    type Iter = ParSobol<T, R>;
+    type Item = Vec<T>;
+    fn into_par_iter(self) -> Self::Iter {
+        ParSobol(Sobol::clone(self))
+    }
+}
+
+impl<T, R> ParallelIterator for ParSobol<T, R>
+where
+    T: SobolType + Send,
+    R: Render<T> + Send,
+{
+    type Item = Vec<T>;
+    fn drive_unindexed<C: UnindexedConsumer<Self::Item>>(self, consumer: C) -> C::Result {
+        match self.0.size_hint().1 {
+            Some(len) => bridge_producer_consumer(len, SobolProducer(self.0), consumer),
+            None => bridge_unindexed(SobolProducer(self.0), consumer),
+        }
+    }
+    fn opt_len(&self) -> Option<usize> {
+        self.0.size_hint().1
+    }
+}
+
+/// Only implemented where the remaining length always fits a `usize`, see `ExactLenType`
+impl<T, R> IndexedParallelIterator for ParSobol<T, R>
+where
+    T: SobolType + Send,
+    T::IT: ExactLenType,
+    R: Render<T> + Send,
+{
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+    fn drive<C: Consumer<Self::Item>>(self, consumer: C) -> C::Result {
+        bridge(self, consumer)
+    }
+    fn with_producer<CB: ProducerCallback<Self::Item>>(self, callback: CB) -> CB::Output {
+        callback.callback(SobolProducer(self.0))
+    }
+}
+
+struct SobolProducer<T: SobolType, R: Render<T>>(Sobol<T, R>);
+
+/// Only bridged as an indexed producer when the remaining length fits a `usize`
+impl<T, R> Producer for SobolProducer<T, R>
+where
+    T: SobolType + Send,
+    R: Render<T> + Send,
+{
+    type Item = Vec<T>;
+    type IntoIter = ProducerIter<T, R>;
+    fn into_iter(self) -> Self::IntoIter {
+        ProducerIter(self.0)
+    }
+    fn split_at(self, index: usize) -> (Self, Self) {
+        let index = self.0.position() + <T::IT as NumCast>::from(index).unwrap();
+        let (head, tail) = self.0.split_at(index);
+        (SobolProducer(head), SobolProducer(tail))
+    }
+}
+
+/// Splits in halves of the remaining index range, whatever its length
+impl<T, R> UnindexedProducer for SobolProducer<T, R>
+where
+    T: SobolType + Send,
+    R: Render<T> + Send,
+{
+    type Item = Vec<T>;
+    fn split(self) -> (Self, Option<Self>) {
+        let half = (self.0.end - self.0.count) >> 1;
+        if half >= T::IT::one() {
+            let index = self.0.count + half;
+            let (head, tail) = self.0.split_at(index);
+            (SobolProducer(head), Some(SobolProducer(tail)))
+        } else {
+            (self, None)
+        }
+    }
+    fn fold_with<F: Folder<Self::Item>>(self, folder: F) -> F {
+        folder.consume_iter(self.0)
+    }
+}
+
+/// The points of an indexed producer, whose remaining length is known to fit a `usize`
+struct ProducerIter<T: SobolType, R: Render<T>>(Sobol<T, R>);
+
+impl<T: SobolType, R: Render<T>> Iterator for ProducerIter<T, R> {
+    type Item = Vec<T>;
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<T: SobolType, R: Render<T>> DoubleEndedIterator for ProducerIter<T, R> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back()
+    }
+}
+
+impl<T: SobolType, R: Render<T>> ExactSizeIterator for ProducerIter<T, R> {}
@@ -0,0 +1,82 @@
+use super::matrix_text::parse_matrix_text;
+use crate::{InternalType, LossyFrom, SobolError};
+use std::borrow::Cow;
+
+/// Published generator matrices over GF(2) of a digital sequence, such as the Niederreiter-Xing
+/// matrices tabulated by Pirsic, which cannot be computed from a handful of parameters. Tables
+/// converted to the format below can be loaded at runtime, or embedded at build time with the
+/// `niederreiter-xing` feature, see `src/params/data/nx/README.md`.
+///
+/// The text format read by `parse` is a list of whitespace-separated integers, with lines
+/// starting with `#` ignored: the number of dimensions, the number of columns of each matrix
+/// and their precision (the number of rows), followed by the columns of each dimension in turn.
+/// Column `r` is the matrix column applied for bit `r` of the point index, as an integer whose
+/// most significant of `precision` bits is the first output digit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GeneratorMatrices {
+    pub dims: usize,
+    pub columns: usize,
+    pub precision: usize,
+    /// The columns of every dimension, `columns` consecutive values per dimension
+    pub data: Cow<'static, [u64]>,
+}
+
+#[cfg(feature = "niederreiter-xing")]
+include!(concat!(env!("OUT_DIR"), "/gen_niederreiter_xing.rs"));
+
+impl GeneratorMatrices {
+    /// Parses generator matrices from the text format described above
+    pub fn parse(text: &str) -> Result<Self, SobolError> {
+        let (dims, columns, precision, data) =
+            parse_matrix_text(text).map_err(|line| SobolError::MatrixFormat { line })?;
+        Ok(GeneratorMatrices {
+            dims,
+            columns,
+            precision,
+            data: Cow::Owned(data),
+        })
+    }
+
+    /// The Niederreiter-Xing matrices embedded at build time (from `src/params/data/nx/*.gz`)
+    /// for exactly `dims` dimensions, or else for the fewest dimensions above it. Returns `None`
+    /// if no embedded table covers `dims` dimensions.
+    #[cfg(feature = "niederreiter-xing")]
+    pub fn niederreiter_xing(dims: usize) -> Option<&'static Self> {
+        NIEDERREITER_XING
+            .iter()
+            .filter(|m| m.dims >= dims)
+            .min_by_key(|m| m.dims)
+    }
+
+    /// The embedded Niederreiter-Xing tables, in the order of their file names
+    #[cfg(feature = "niederreiter-xing")]
+    pub fn niederreiter_xing_tables() -> &'static [Self] {
+        NIEDERREITER_XING
+    }
+
+    /// The columns of the matrix of the given (zero-based) dimension
+    #[inline]
+    pub fn matrix(&self, dim: usize) -> &[u64] {
+        &self.data[dim * self.columns..(dim + 1) * self.columns]
+    }
+
+    /// The columns of the first `dims` matrices as values of an internal type, with the first
+    /// output digit in the most significant bit. Digits beyond the width of the type are
+    /// dropped.
+    pub fn to_columns<IT: InternalType>(&self, dims: usize) -> Vec<Vec<IT>> {
+        (0..dims.min(self.dims))
+            .map(|dim| {
+                self.matrix(dim)
+                    .iter()
+                    .map(|col| {
+                        if IT::BITS >= self.precision {
+                            <IT as LossyFrom<u64>>::lossy_from(*col) << (IT::BITS - self.precision)
+                        } else {
+                            <IT as LossyFrom<u64>>::lossy_from(*col >> (self.precision - IT::BITS))
+                        }
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+}
@@ -6,6 +6,9 @@ pub struct JoeKuoD6 {
     pub max_dims: usize,
 }
 
+/// Cache key of the embedded parameter sets, which all borrow from the same static table
+const JOE_KUO_D6_KEY: u64 = u64::from_be_bytes(*b"jk-new-6");
+
 include!(concat!(env!("OUT_DIR"), "/gen_joe_kuo_d6.rs"));
 impl JoeKuoD6 {
     /// Load parameter values supporting up to **1000** dimensions
@@ -29,6 +32,13 @@ impl SobolParams<u32> for JoeKuoD6 {
     fn max_dims(&self) -> usize {
         self.max_dims
     }
+
+    #[inline]
+    fn cache_key(&self) -> Option<u64> {
+        // The static has a single address, and any parameters borrowed from its start hold the
+        // same values. Other parameters are not cached.
+        core::ptr::eq(self.dim_params.as_ptr(), JOE_KUO_D6_DIMS.as_ptr()).then_some(JOE_KUO_D6_KEY)
+    }
 }
 
 /// Parameters for a single dimension
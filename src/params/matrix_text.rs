@@ -0,0 +1,47 @@
+//! The text format of `GeneratorMatrices`, shared with the build script which embeds published
+//! tables. Depends on `std` alone so that `build.rs` can include it as a module.
+
+/// Generator matrices as read from text: the number of dimensions, columns and precision, and
+/// the columns of every dimension in turn
+pub type MatrixText = (usize, usize, usize, Vec<u64>);
+
+/// Parses generator matrices from text, or returns the (one-based) line of the first error
+pub fn parse_matrix_text(text: &str) -> Result<MatrixText, usize> {
+    let tokens: Vec<(usize, &str)> = text
+        .lines()
+        .enumerate()
+        .filter(|(_, l)| !l.trim_start().starts_with('#'))
+        .flat_map(|(i, l)| l.split_whitespace().map(move |t| (i + 1, t)))
+        .collect();
+    let end = text.lines().count() + 1;
+    let value = |i: usize| -> Result<(usize, u64), usize> {
+        let (line, token) = tokens.get(i).ok_or(end)?;
+        let val = token.parse().map_err(|_| *line)?;
+        Ok((*line, val))
+    };
+
+    let (_, dims) = value(0)?;
+    let (_, columns) = value(1)?;
+    let (line, precision) = value(2)?;
+    if precision == 0 || precision > 64 {
+        return Err(line);
+    }
+    // The header is untrusted, so sizes which do not fit in memory are rejected rather than
+    // overflowing
+    let size = |val: u64| usize::try_from(val).map_err(|_| line);
+    let (dims, columns) = (size(dims)?, size(columns)?);
+    let len = dims
+        .checked_mul(columns)
+        .filter(|len| *len < usize::MAX - 3)
+        .ok_or(line)?;
+    if let Some((line, _)) = tokens.get(3 + len) {
+        return Err(*line);
+    }
+    let data = (3..3 + len)
+        .map(|i| match value(i)? {
+            (line, col) if precision < 64 && col >> precision != 0 => Err(line),
+            (_, col) => Ok(col),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok((dims, columns, precision as usize, data))
+}
@@ -1,3 +1,6 @@
+mod generator_matrices;
 mod joe_kuo_d6;
+mod matrix_text;
 
+pub use self::generator_matrices::GeneratorMatrices;
 pub use self::joe_kuo_d6::*;
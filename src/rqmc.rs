@@ -0,0 +1,213 @@
+use crate::scramble::{RandomSource, SplitMix64};
+use crate::{Render, Sobol, SobolType};
+use statrs::distribution::{ContinuousCDF, StudentsT};
+
+/// Domain separation constant mixed into the master seed of a runner, so that replicate seeds
+/// are not simply the outputs of a SplitMix64 generator seeded with the master seed itself
+const REPLICATE_DOMAIN: u64 = 0x7271_6d63_7265_706c;
+
+/// Randomization applied to each replicate of a randomized quasi-Monte Carlo estimate
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Randomization {
+    /// Random digital shift, see `Sobol::with_digital_shift`
+    DigitalShift,
+    /// Cranley-Patterson random rotation on the grid of the sequence's resolution, see
+    /// `Sobol::with_random_rotation`
+    RandomRotation,
+    /// Matousek linear matrix scrambling followed by a digital shift
+    LinearScramble,
+    /// Matousek and Faure-Tezuka (left and right) linear scrambling followed by a digital shift
+    FullLinearScramble,
+    /// Owen nested uniform scrambling, see `Sobol::with_owen_scramble`
+    #[default]
+    OwenScramble,
+}
+
+impl Randomization {
+    /// Randomizes the given sequence with this scheme and seed
+    pub fn apply<T: SobolType, R: Render<T>>(self, seq: Sobol<T, R>, seed: u64) -> Sobol<T, R> {
+        match self {
+            Randomization::DigitalShift => seq.with_digital_shift(seed),
+            Randomization::RandomRotation => seq.with_random_rotation(seed),
+            Randomization::LinearScramble => {
+                let mut rng = SplitMix64::new(seed);
+                seq.with_linear_scramble_from(&mut rng)
+                    .with_digital_shift_from(&mut rng)
+            }
+            Randomization::FullLinearScramble => {
+                let mut rng = SplitMix64::new(seed);
+                seq.with_linear_scramble_from(&mut rng)
+                    .with_faure_tezuka_scramble_from(&mut rng)
+                    .with_digital_shift_from(&mut rng)
+            }
+            Randomization::OwenScramble => seq.with_owen_scramble(seed),
+        }
+    }
+}
+
+/// Replicated randomized quasi-Monte Carlo runner. Each replicate independently randomizes the
+/// base sequence, averages a function over its points, and the spread of the replicate means
+/// gives the standard error of the overall estimate.
+pub struct Rqmc<T: SobolType, R: Render<T>> {
+    base: Sobol<T, R>,
+    randomization: Randomization,
+    replicates: usize,
+    points: usize,
+    seed: u64,
+    confidence: f64,
+}
+
+impl<T: SobolType, R: Render<T>> Clone for Rqmc<T, R> {
+    fn clone(&self) -> Self {
+        Rqmc {
+            base: self.base.clone(),
+            randomization: self.randomization,
+            replicates: self.replicates,
+            points: self.points,
+            seed: self.seed,
+            confidence: self.confidence,
+        }
+    }
+}
+
+/// Result of a replicated randomized quasi-Monte Carlo run
+#[derive(Debug, Clone, PartialEq)]
+pub struct RqmcEstimate {
+    /// Mean of the replicate estimates
+    pub mean: f64,
+    /// Standard error of the mean across replicates
+    pub std_error: f64,
+    /// Two-sided Student-t confidence interval around the mean
+    pub interval: (f64, f64),
+    /// Estimate of each replicate, in order
+    pub replicates: Vec<f64>,
+}
+
+impl<T: SobolType, R: Render<T>> Rqmc<T, R> {
+    /// Constructs a runner over the given sequence, by default with 16 replicates of 1024
+    /// points, seed 0 and a 95% confidence interval. Any randomization already applied to the
+    /// base sequence is kept underneath that of each replicate.
+    pub fn new(base: Sobol<T, R>, randomization: Randomization) -> Self {
+        Rqmc {
+            base,
+            randomization,
+            replicates: 16,
+            points: 1 << 10,
+            seed: 0,
+            confidence: 0.95,
+        }
+    }
+
+    /// Sets the number of independently randomized replicates, which must be at least two
+    pub fn with_replicates(mut self, replicates: usize) -> Self {
+        assert!(replicates >= 2, "at least two replicates are required");
+        self.replicates = replicates;
+        self
+    }
+
+    /// Sets the number of points evaluated per replicate. Powers of two preserve the balance
+    /// properties of the sequence.
+    pub fn with_points(mut self, points: usize) -> Self {
+        assert!(points > 0, "at least one point is required");
+        self.points = points;
+        self
+    }
+
+    /// Sets the master seed from which the seed of every replicate is derived
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Sets the confidence level of the reported interval, e.g. `0.99`
+    pub fn with_confidence(mut self, confidence: f64) -> Self {
+        assert!(
+            confidence > 0.0 && confidence < 1.0,
+            "confidence level must lie strictly between 0 and 1"
+        );
+        self.confidence = confidence;
+        self
+    }
+
+    /// The randomization applied to each replicate
+    #[inline]
+    pub fn randomization(&self) -> Randomization {
+        self.randomization
+    }
+
+    /// The number of independently randomized replicates
+    #[inline]
+    pub fn replicates(&self) -> usize {
+        self.replicates
+    }
+
+    /// The number of points evaluated per replicate
+    #[inline]
+    pub fn points(&self) -> usize {
+        self.points
+    }
+
+    /// The master seed from which the seed of every replicate is derived
+    #[inline]
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// The confidence level of the reported interval
+    #[inline]
+    pub fn confidence(&self) -> f64 {
+        self.confidence
+    }
+
+    /// Seeds of each replicate. They are successive outputs of a SplitMix64 generator keyed by
+    /// the master seed, rather than e.g. `seed + r`, so replicates are never correlated through
+    /// overlapping generator streams.
+    pub fn replicate_seeds(&self) -> Vec<u64> {
+        let mut rng = SplitMix64::new(self.seed ^ REPLICATE_DOMAIN);
+        (0..self.replicates).map(|_| rng.next_u64()).collect()
+    }
+
+    /// Estimates the mean of `f` over the unit cube (or whatever the render maps it to). If the
+    /// base sequence holds fewer than `points` remaining points, each replicate averages over
+    /// the points it has.
+    ///
+    /// Panics if the base sequence has no points left.
+    pub fn run<F: FnMut(&[T]) -> f64>(&self, mut f: F) -> RqmcEstimate
+    where
+        T: Default + Clone,
+    {
+        assert!(
+            self.base.size_hint().1 != Some(0),
+            "base sequence has no points left to evaluate"
+        );
+        let mut point = vec![T::default(); self.base.dims];
+        let replicates: Vec<f64> = self
+            .replicate_seeds()
+            .into_iter()
+            .map(|seed| {
+                let mut seq = self.randomization.apply(self.base.clone(), seed);
+                let mut sum = 0.0;
+                let mut n = 0;
+                while n < self.points && seq.next_into(&mut point) {
+                    sum += f(&point);
+                    n += 1;
+                }
+                sum / n as f64
+            })
+            .collect();
+
+        let r = replicates.len() as f64;
+        let mean = replicates.iter().sum::<f64>() / r;
+        let variance = replicates.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (r - 1.0);
+        let std_error = (variance / r).sqrt();
+        let t = StudentsT::new(0.0, 1.0, r - 1.0)
+            .expect("replicate count yields a valid Student-t distribution")
+            .inverse_cdf(0.5 + self.confidence / 2.0);
+        RqmcEstimate {
+            mean,
+            std_error,
+            interval: (mean - t * std_error, mean + t * std_error),
+            replicates,
+        }
+    }
+}
@@ -0,0 +1,176 @@
+use crate::InternalType;
+
+/// Randomizations applied to the internal values of a sequence before they are rendered
+#[derive(Debug, Clone)]
+pub(crate) struct Scramble<IT> {
+    /// Per-dimension seeds of the nested uniform scramble, or empty if not scrambled
+    owen: Vec<IT>,
+    /// Mask of the bits within the resolution of the sequence
+    owen_mask: IT,
+    /// Per-dimension words XORed onto internal values, or empty if not shifted
+    shift: Vec<IT>,
+    /// Per-dimension words added (modulo 2^BITS) to internal values, or empty if not rotated
+    rotation: Vec<IT>,
+}
+
+impl<IT: InternalType> Default for Scramble<IT> {
+    fn default() -> Self {
+        Scramble {
+            owen: Vec::new(),
+            owen_mask: IT::zero(),
+            shift: Vec::new(),
+            rotation: Vec::new(),
+        }
+    }
+}
+
+impl<IT: InternalType> Scramble<IT> {
+    /// Draws one digital shift word per dimension, keeping only the top `resolution` bits so
+    /// rendered values retain the resolution of the sequence
+    pub fn set_digital_shift(
+        &mut self,
+        dims: usize,
+        resolution: usize,
+        rng: &mut impl RandomSource,
+    ) {
+        let mask = resolution_mask::<IT>(resolution);
+        self.shift = (0..dims).map(|_| rng.next_word::<IT>() & mask).collect();
+    }
+
+    /// Draws one rotation word per dimension, keeping only the top `resolution` bits so that
+    /// rotated values stay on the grid of the sequence and floats render strictly below one.
+    /// The rotation is therefore uniform over the grid rather than over `[0,1)`.
+    pub fn set_rotation(&mut self, dims: usize, resolution: usize, rng: &mut impl RandomSource) {
+        let mask = resolution_mask::<IT>(resolution);
+        self.rotation = (0..dims).map(|_| rng.next_word::<IT>() & mask).collect();
+    }
+
+    /// Derives one nested uniform scrambling seed per dimension from the given 64-bit seeds
+    pub fn set_owen(&mut self, seeds: impl IntoIterator<Item = u64>, resolution: usize) {
+        let words = seeds
+            .into_iter()
+            .map(|seed| SplitMix64::new(seed).next_word())
+            .collect();
+        self.set_owen_words(words, resolution);
+    }
+
+    /// Sets the nested uniform scrambling words of each dimension directly
+    pub fn set_owen_words(&mut self, words: Vec<IT>, resolution: usize) {
+        self.owen = words;
+        self.owen_mask = resolution_mask::<IT>(resolution);
+    }
+
+    #[inline]
+    pub fn apply(&self, dim: usize, val: IT) -> IT {
+        let val = match self.owen.get(dim) {
+            Some(seed) => owen_scramble(val, *seed) & self.owen_mask,
+            None => val,
+        };
+        let val = match self.shift.get(dim) {
+            Some(shift) => val ^ *shift,
+            None => val,
+        };
+        match self.rotation.get(dim) {
+            Some(rotation) => val.wrapping_add(rotation),
+            None => val,
+        }
+    }
+}
+
+/// Nested uniform (Owen) scrambling of an internal value: each bit is flipped according to a
+/// random function of the bits above it. Rather than storing permutation trees, this uses
+/// Burley's hash-based variant of the Laine-Karras permutation, which only lets bits influence
+/// less significant bits once the value is bit-reversed. It applies to every internal width;
+/// narrower types use the truncated constants, which remain even as required.
+#[inline]
+pub(crate) fn owen_scramble<IT: InternalType>(val: IT, seed: IT) -> IT {
+    let mut x = val.reverse_bits();
+    x = x ^ x.wrapping_mul(&IT::lossy_from(0x3d20_adea));
+    x = x.wrapping_add(&seed);
+    x = x.wrapping_mul(&((seed >> (IT::BITS / 2)) | IT::one()));
+    x = x ^ x.wrapping_mul(&IT::lossy_from(0x0552_6c56));
+    x = x ^ x.wrapping_mul(&IT::lossy_from(0x53a2_2864));
+    x.reverse_bits()
+}
+
+/// Mask of the top `resolution` bits of an internal value
+#[inline]
+pub(crate) fn resolution_mask<IT: InternalType>(resolution: usize) -> IT {
+    if resolution == 0 {
+        IT::zero()
+    } else {
+        IT::max_value() << (IT::BITS - resolution)
+    }
+}
+
+/// Source of the random bits used to randomize sequences. With the `rand_core` feature enabled
+/// any `rand_core::RngCore` can be wrapped in `RngSource`, so randomization can be driven by
+/// existing seeded generators; otherwise `SplitMix64` serves as the built-in default.
+pub trait RandomSource {
+    /// Draws 64 uniformly distributed random bits
+    fn next_u64(&mut self) -> u64;
+
+    /// Draws a uniformly distributed word of any internal type, taking as many 64-bit outputs
+    /// as needed to fill it
+    #[inline]
+    fn next_word<IT: InternalType>(&mut self) -> IT
+    where
+        Self: Sized,
+    {
+        if IT::BITS > 64 {
+            let hi = IT::lossy_from(self.next_u64());
+            (hi << 64) | IT::lossy_from(self.next_u64())
+        } else {
+            IT::lossy_from(self.next_u64())
+        }
+    }
+}
+
+/// Adapts a `rand_core::RngCore` into a `RandomSource`. Wrap a mutable reference to keep using
+/// the generator afterwards.
+#[cfg(feature = "rand_core")]
+#[derive(Debug, Clone)]
+pub struct RngSource<G>(pub G);
+
+#[cfg(feature = "rand_core")]
+impl<G: rand_core::RngCore> RandomSource for RngSource<G> {
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        self.0.next_u64()
+    }
+}
+
+/// Increment of the SplitMix64 state per output
+const GAMMA: u64 = 0x9e37_79b9_7f4a_7c15;
+
+/// Counter-based SplitMix64 generator. Each output is a fixed function of the seed and the
+/// number of outputs drawn so far, computed with wrapping 64-bit integer arithmetic only, so
+/// streams are identical across platforms and endianness. This is the generator behind every
+/// seed accepted by this crate.
+#[derive(Debug, Clone)]
+pub struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    pub fn new(seed: u64) -> Self {
+        SplitMix64 { state: seed }
+    }
+
+    /// Advances the generator by `n` outputs in constant time
+    #[inline]
+    pub fn skip(&mut self, n: u64) {
+        self.state = self.state.wrapping_add(GAMMA.wrapping_mul(n));
+    }
+}
+
+impl RandomSource for SplitMix64 {
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(GAMMA);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+}
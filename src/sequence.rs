@@ -0,0 +1,195 @@
+use crate::{BaseBSequence, Halton, PaddedSobol, Render, Sobol, SobolN, SobolType};
+use num_traits::{NumCast, One, ToPrimitive};
+
+/// A generator of low-discrepancy points, implemented by every sequence in this crate so that
+/// tools can be written once and run against any of them. Generators producing fixed-point
+/// values of a `SobolType`'s internal type can reuse the existing `Render`s.
+pub trait LowDiscrepancy {
+    /// The type of each component of a point
+    type Item;
+
+    /// The number of dimensions of each point
+    fn dims(&self) -> usize;
+
+    /// The number of points left to generate, or `None` if it exceeds `usize::MAX`
+    fn remaining(&self) -> Option<usize>;
+
+    /// Writes the next point into the given buffer, returning `false` (leaving the buffer
+    /// untouched) once the sequence is exhausted.
+    ///
+    /// Panics if the length of the buffer differs from `dims()`.
+    fn next_into(&mut self, out: &mut [Self::Item]) -> bool;
+}
+
+/// A low-discrepancy generator which can compute the point at any index directly
+pub trait RandomAccess: LowDiscrepancy {
+    /// Writes the point at the given zero-based index into the given buffer, returning `false`
+    /// (leaving the buffer untouched) if the index is beyond the end of the sequence. Does not
+    /// affect the position of the generator.
+    ///
+    /// Panics if the length of the buffer differs from `dims()`.
+    fn point_into(&self, index: u64, out: &mut [Self::Item]) -> bool;
+}
+
+impl<T: SobolType, R: Render<T>> LowDiscrepancy for Sobol<T, R> {
+    type Item = T;
+
+    #[inline]
+    fn dims(&self) -> usize {
+        self.dims
+    }
+
+    #[inline]
+    fn remaining(&self) -> Option<usize> {
+        self.size_hint().1
+    }
+
+    #[inline]
+    fn next_into(&mut self, out: &mut [T]) -> bool {
+        Sobol::next_into(self, out)
+    }
+}
+
+impl<T: SobolType, R: Render<T>> RandomAccess for Sobol<T, R> {
+    #[inline]
+    fn point_into(&self, index: u64, out: &mut [T]) -> bool {
+        match <T::IT as NumCast>::from(index) {
+            Some(index) => self.point_at_into(index, out),
+            None => {
+                self.check_buf_len(out.len());
+                false
+            }
+        }
+    }
+}
+
+impl<T: SobolType, R: Render<T>> LowDiscrepancy for PaddedSobol<T, R> {
+    type Item = T;
+
+    #[inline]
+    fn dims(&self) -> usize {
+        self.dims
+    }
+
+    #[inline]
+    fn remaining(&self) -> Option<usize> {
+        (self.max_len - self.count).to_usize()
+    }
+
+    #[inline]
+    fn next_into(&mut self, out: &mut [T]) -> bool {
+        let index = self.count;
+        if self.point_at_into(index, out) {
+            self.seek(index + T::IT::one());
+            true
+        } else {
+            false
+        }
+    }
+}
+
+impl<T: SobolType, R: Render<T>> RandomAccess for PaddedSobol<T, R> {
+    #[inline]
+    fn point_into(&self, index: u64, out: &mut [T]) -> bool {
+        match <T::IT as NumCast>::from(index) {
+            Some(index) => self.point_at_into(index, out),
+            None => self.point_at_into(self.max_len, out),
+        }
+    }
+}
+
+impl<T: SobolType, const D: usize, R: Render<T>> LowDiscrepancy for SobolN<T, D, R> {
+    type Item = T;
+
+    #[inline]
+    fn dims(&self) -> usize {
+        D
+    }
+
+    #[inline]
+    fn remaining(&self) -> Option<usize> {
+        self.size_hint().1
+    }
+
+    #[inline]
+    fn next_into(&mut self, out: &mut [T]) -> bool {
+        let out: &mut [T; D] = out
+            .try_into()
+            .expect("buffer length must match the number of sequence dimensions");
+        match self.next() {
+            Some(point) => {
+                *out = point;
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+impl<T: SobolType, const D: usize, R: Render<T>> RandomAccess for SobolN<T, D, R> {
+    #[inline]
+    fn point_into(&self, index: u64, out: &mut [T]) -> bool {
+        let out: &mut [T; D] = out
+            .try_into()
+            .expect("buffer length must match the number of sequence dimensions");
+        match <T::IT as NumCast>::from(index).and_then(|index| self.point_at(index)) {
+            Some(point) => {
+                *out = point;
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+impl<T: SobolType, R: Render<T>> LowDiscrepancy for Halton<T, R> {
+    type Item = T;
+
+    #[inline]
+    fn dims(&self) -> usize {
+        self.dims
+    }
+
+    #[inline]
+    fn remaining(&self) -> Option<usize> {
+        self.size_hint().1
+    }
+
+    #[inline]
+    fn next_into(&mut self, out: &mut [T]) -> bool {
+        Halton::next_into(self, out)
+    }
+}
+
+impl<T: SobolType, R: Render<T>> RandomAccess for Halton<T, R> {
+    #[inline]
+    fn point_into(&self, index: u64, out: &mut [T]) -> bool {
+        self.point_at_into(index, out)
+    }
+}
+
+impl<T: SobolType, R: Render<T>> LowDiscrepancy for BaseBSequence<T, R> {
+    type Item = T;
+
+    #[inline]
+    fn dims(&self) -> usize {
+        BaseBSequence::dims(self)
+    }
+
+    #[inline]
+    fn remaining(&self) -> Option<usize> {
+        self.size_hint().1
+    }
+
+    #[inline]
+    fn next_into(&mut self, out: &mut [T]) -> bool {
+        BaseBSequence::next_into(self, out)
+    }
+}
+
+impl<T: SobolType, R: Render<T>> RandomAccess for BaseBSequence<T, R> {
+    #[inline]
+    fn point_into(&self, index: u64, out: &mut [T]) -> bool {
+        self.point_at_into(index, out)
+    }
+}
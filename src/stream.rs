@@ -0,0 +1,78 @@
+use crate::scramble::{RandomSource, SplitMix64};
+use crate::{Randomization, Render, Sobol, SobolType};
+
+/// Domain separation constant mixed into the master seed of a factory, so that a factory and an
+/// `Rqmc` runner given the same master seed do not start from the same generator state
+const STREAM_DOMAIN: u64 = 0x7374_7265_616d_6964;
+
+/// Hands out statistically independent randomized copies of one sequence, addressable by a
+/// stream id (e.g. an MPI rank or scenario number) and all derived from a single master seed.
+/// Whereas `Sobol::chunks` partitions one sequence, each stream here is a complete sequence with
+/// its own randomization. Streams share the direction values of the base sequence unless the
+/// randomization itself transforms them.
+pub struct StreamFactory<T: SobolType, R: Render<T>> {
+    base: Sobol<T, R>,
+    randomization: Randomization,
+    master_seed: u64,
+}
+
+impl<T: SobolType, R: Render<T>> Clone for StreamFactory<T, R> {
+    fn clone(&self) -> Self {
+        StreamFactory {
+            base: self.base.clone(),
+            randomization: self.randomization,
+            master_seed: self.master_seed,
+        }
+    }
+}
+
+impl<T: SobolType, R: Render<T>> StreamFactory<T, R> {
+    /// Constructs a factory of streams randomizing the given sequence with Owen scrambling
+    pub fn new(base: Sobol<T, R>, master_seed: u64) -> Self {
+        StreamFactory {
+            base,
+            randomization: Randomization::default(),
+            master_seed,
+        }
+    }
+
+    /// Sets the randomization applied to each stream
+    pub fn with_randomization(mut self, randomization: Randomization) -> Self {
+        self.randomization = randomization;
+        self
+    }
+
+    /// The randomization applied to each stream
+    #[inline]
+    pub fn randomization(&self) -> Randomization {
+        self.randomization
+    }
+
+    /// The master seed from which the seed of every stream is derived
+    #[inline]
+    pub fn master_seed(&self) -> u64 {
+        self.master_seed
+    }
+
+    /// The seed of the given stream: output number `id` of a SplitMix64 generator keyed by the
+    /// master seed. It is computed in constant time, so any stream can be created directly
+    /// without creating those before it. SplitMix64 outputs are a bijection of its internal
+    /// counter, so the streams of one factory all get distinct seeds; nothing prevents them from
+    /// coinciding with seeds drawn elsewhere, which is merely unlikely.
+    pub fn stream_seed(&self, id: u64) -> u64 {
+        let mut rng = SplitMix64::new(self.master_seed ^ STREAM_DOMAIN);
+        rng.skip(id);
+        rng.next_u64()
+    }
+
+    /// Creates the given stream, positioned where the base sequence is
+    pub fn stream(&self, id: u64) -> Sobol<T, R> {
+        self.randomization
+            .apply(self.base.clone(), self.stream_seed(id))
+    }
+
+    /// Creates consecutive streams starting from the given id
+    pub fn streams(&self, first: u64) -> impl Iterator<Item = Sobol<T, R>> + '_ {
+        (first..).map(|id| self.stream(id))
+    }
+}
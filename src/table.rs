@@ -0,0 +1,380 @@
+use crate::{
+    InternalType, LossyFrom, ParamDimension, SobolError, SobolParams, SobolType, niederreiter,
+    params::GeneratorMatrices,
+    scramble::{RandomSource, SplitMix64},
+};
+use core::any::{Any, TypeId};
+use num_traits::{Bounded, One, PrimInt, Zero};
+use std::{
+    collections::HashMap,
+    sync::{Arc, LazyLock, Mutex},
+};
+
+/// Direction values for every dimension of a sequence at a given resolution. Tables are
+/// immutable once built and are shared between generators via `Arc`, so constructing or
+/// cloning a `Sobol` from an existing table only copies its cursor state.
+///
+/// Values are stored transposed (one row per bit, one column per dimension) for better cache
+/// locality when advancing the sequence.
+pub struct DirectionTable<T: SobolType> {
+    dims: usize,
+    resolution: usize,
+    vals: Vec<T::IT>,
+}
+
+/// Cache key made up of the sequence type, parameter type, parameter `cache_key`, maximum
+/// dims of the parameters, dims and resolution
+type CacheKey = (TypeId, TypeId, u64, usize, usize, usize);
+
+static CACHE: LazyLock<Mutex<HashMap<CacheKey, Arc<dyn Any + Send + Sync>>>> =
+    LazyLock::new(Default::default);
+
+impl<T: SobolType> DirectionTable<T> {
+    /// Builds the direction values for the first `dims` dimensions of the given parameters.
+    /// Resolution defaults to (and is capped at) `T::MAX_RESOLUTION`.
+    pub fn new<P, Param: SobolParams<P>>(
+        dims: usize,
+        params: &Param,
+        resolution: Option<usize>,
+    ) -> Result<Self, SobolError>
+    where
+        T::IT: LossyFrom<P>,
+    {
+        let res = resolution
+            .filter(|res| *res <= T::MAX_RESOLUTION)
+            .unwrap_or(T::MAX_RESOLUTION);
+        let max_dims = params.max_dims();
+        if dims > max_dims {
+            return Err(SobolError::MaxDim { dims, max_dims });
+        }
+
+        let dir_vals = Self::init_direction_vals::<P, Param>(dims, res, params);
+        Ok(Self::transpose(&dir_vals, res))
+    }
+
+    /// Builds the direction values for an explicit list of (one-based) dimensions of the given
+    /// parameters, in the order given. This allows any subset or permutation of parameter
+    /// dimensions to be used, e.g. `[1, 2, 7, 40]` or `5..15`, so that dimension assignments
+    /// stay stable as a model gains inputs. Resolution defaults to (and is capped at)
+    /// `T::MAX_RESOLUTION`.
+    pub fn with_dims<P, Param: SobolParams<P>>(
+        dims: impl IntoIterator<Item = usize>,
+        params: &Param,
+        resolution: Option<usize>,
+    ) -> Result<Self, SobolError>
+    where
+        T::IT: LossyFrom<P>,
+    {
+        let res = resolution
+            .filter(|res| *res <= T::MAX_RESOLUTION)
+            .unwrap_or(T::MAX_RESOLUTION);
+        let max_dims = params.max_dims();
+        let dir_vals = dims
+            .into_iter()
+            .map(|dim| {
+                if dim == 0 || dim > max_dims {
+                    Err(SobolError::InvalidDim { dim, max_dims })
+                } else {
+                    Ok(Self::init_dim_direction_vals::<P, Param>(dim, res, params))
+                }
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self::transpose(&dir_vals, res))
+    }
+
+    /// Builds a table from explicit generator matrices over GF(2), turning `Sobol` into a generic
+    /// digital sequence. `matrices[dim][r]` is column `r` of the matrix of dimension `dim`,
+    /// i.e. the value XORed in when bit `r` of the point index is set, with the first output
+    /// digit in the most significant bit. Each matrix needs at least `resolution` columns;
+    /// resolution defaults to (and is capped at) `T::MAX_RESOLUTION`, and digits beyond it are
+    /// dropped.
+    pub fn from_matrices(
+        matrices: &[Vec<T::IT>],
+        resolution: Option<usize>,
+    ) -> Result<Self, SobolError> {
+        let res = resolution
+            .filter(|res| *res <= T::MAX_RESOLUTION)
+            .unwrap_or(T::MAX_RESOLUTION);
+        let mask = if res == 0 {
+            T::IT::zero()
+        } else {
+            T::IT::max_value() << (T::IT::BITS - res)
+        };
+        let dir_vals = matrices
+            .iter()
+            .enumerate()
+            .map(|(dim, columns)| {
+                if columns.len() < res {
+                    Err(SobolError::MatrixSize {
+                        dim,
+                        columns: columns.len(),
+                        resolution: res,
+                    })
+                } else {
+                    Ok(columns[..res].iter().map(|c| *c & mask).collect())
+                }
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self::transpose(&dir_vals, res))
+    }
+
+    /// Builds a table from the first `dims` of the given published generator matrices (see
+    /// `GeneratorMatrices`). Resolution defaults to the number of columns of the matrices,
+    /// capped at `T::MAX_RESOLUTION`.
+    pub fn from_generator_matrices(
+        matrices: &GeneratorMatrices,
+        dims: usize,
+        resolution: Option<usize>,
+    ) -> Result<Self, SobolError> {
+        if dims > matrices.dims {
+            return Err(SobolError::MaxDim {
+                dims,
+                max_dims: matrices.dims,
+            });
+        }
+        let res = resolution.unwrap_or(matrices.columns.min(T::MAX_RESOLUTION));
+        Self::from_matrices(&matrices.to_columns(dims), Some(res))
+    }
+
+    /// Builds the generator matrices of the base 2 Niederreiter sequence for the first `dims`
+    /// dimensions, constructed from the irreducible polynomials over GF(2) in order of degree.
+    /// Its t-value grows more slowly with dimension than that of Sobol's sequence. The first two
+    /// dimensions coincide with Sobol's. Resolution defaults to (and is capped at)
+    /// `T::MAX_RESOLUTION`.
+    pub fn niederreiter(dims: usize, resolution: Option<usize>) -> Self {
+        let res = resolution
+            .filter(|res| *res <= T::MAX_RESOLUTION)
+            .unwrap_or(T::MAX_RESOLUTION);
+        Self::transpose(&niederreiter::generator_matrices(dims, res), res)
+    }
+
+    /// Transposes per-dimension direction values for better cache locality
+    fn transpose(dir_vals: &[Vec<T::IT>], resolution: usize) -> Self {
+        let vals = (0..resolution)
+            .flat_map(|i| dir_vals.iter().map(move |inner| inner[i]))
+            .collect();
+        DirectionTable {
+            dims: dir_vals.len(),
+            resolution,
+            vals,
+        }
+    }
+
+    /// Same as `new` but returns a table shared through a process-wide cache keyed by
+    /// parameters, dims and resolution, so that repeated calls only pay for the first one.
+    /// Parameters which return `None` from `SobolParams::cache_key` are never cached. Note that
+    /// `Sobol::new` always builds its own table; pass a cached table to `Sobol::from_table` to
+    /// share it.
+    pub fn cached<P, Param: SobolParams<P> + 'static>(
+        dims: usize,
+        params: &Param,
+        resolution: Option<usize>,
+    ) -> Result<Arc<Self>, SobolError>
+    where
+        T: 'static,
+        T::IT: LossyFrom<P>,
+    {
+        let Some(params_key) = params.cache_key() else {
+            return Self::new(dims, params, resolution).map(Arc::new);
+        };
+        let res = resolution
+            .filter(|res| *res <= T::MAX_RESOLUTION)
+            .unwrap_or(T::MAX_RESOLUTION);
+        let key = (
+            TypeId::of::<T>(),
+            TypeId::of::<Param>(),
+            params_key,
+            params.max_dims(),
+            dims,
+            res,
+        );
+
+        if let Some(table) = CACHE.lock().unwrap().get(&key) {
+            return Ok(table.clone().downcast().unwrap());
+        }
+        // Build outside the lock so other tables can be looked up meanwhile. Should another
+        // thread insert the same table first, its copy is kept and ours is dropped.
+        let table = Arc::new(Self::new(dims, params, Some(res))?);
+        let cached = CACHE.lock().unwrap().entry(key).or_insert(table).clone();
+        Ok(cached.downcast().unwrap())
+    }
+
+    /// Removes all tables from the process-wide cache. Tables still in use are unaffected.
+    pub fn clear_cache() {
+        CACHE.lock().unwrap().clear();
+    }
+
+    /// The number of dimensions
+    #[inline]
+    pub fn dims(&self) -> usize {
+        self.dims
+    }
+
+    /// The number of bits of each direction value
+    #[inline]
+    pub fn resolution(&self) -> usize {
+        self.resolution
+    }
+
+    /// The direction values of bit `bit` (zero-based, most significant first) for every
+    /// dimension
+    #[inline]
+    pub fn row(&self, bit: usize) -> &[T::IT] {
+        &self.vals[bit * self.dims..(bit + 1) * self.dims]
+    }
+
+    /// The direction value of bit `bit` for dimension `dim`
+    #[inline]
+    pub fn get(&self, bit: usize, dim: usize) -> T::IT {
+        self.vals[bit * self.dims + dim]
+    }
+
+    /// Returns a copy of this table with Matoušek's random linear matrix scrambling applied.
+    /// The generator matrix of each dimension is left-multiplied by a random lower-triangular
+    /// binary matrix with unit diagonal drawn from the given seed, i.e. each output digit is
+    /// XORed with a random combination of the more significant digits. The net structure is
+    /// preserved, and since only the table is transformed the cost of generating each point is
+    /// unchanged.
+    pub fn linear_scramble(&self, seed: u64) -> Self {
+        self.linear_scramble_from(&mut SplitMix64::new(seed))
+    }
+
+    /// Same as `linear_scramble` but drawing the scrambling matrices from the given source
+    pub fn linear_scramble_from(&self, rng: &mut impl RandomSource) -> Self {
+        let bits = T::IT::BITS;
+        let mut table = self.clone();
+        for dim in 0..self.dims {
+            // Row `r` (counting digits from the most significant) selects digit `r` itself and
+            // a random subset of the digits above it
+            let rows: Vec<T::IT> = (0..self.resolution)
+                .map(|r| {
+                    let digit = T::IT::one() << (bits - 1 - r);
+                    if r == 0 {
+                        digit
+                    } else {
+                        let above = T::IT::max_value() << (bits - r);
+                        (rng.next_word::<T::IT>() & above) | digit
+                    }
+                })
+                .collect();
+            for bit in 0..self.resolution {
+                let v = self.get(bit, dim);
+                let mut scrambled = T::IT::zero();
+                for (r, row) in rows.iter().enumerate() {
+                    if (*row & v).count_ones() & 1 == 1 {
+                        scrambled = scrambled | (T::IT::one() << (bits - 1 - r));
+                    }
+                }
+                table.vals[bit * self.dims + dim] = scrambled;
+            }
+        }
+        table
+    }
+
+    /// Returns a copy of this table with Faure-Tezuka scrambling applied. The generator matrix
+    /// of every dimension is right-multiplied by the same random upper-triangular binary matrix
+    /// with unit diagonal drawn from the given seed, i.e. each digit of the point index is
+    /// XORed with a random combination of the more significant index digits before it selects
+    /// direction values. This permutes the points within every block of `2^m` indices starting
+    /// at zero, and composes with `linear_scramble` (in either order) into a full linear
+    /// scramble. As with `linear_scramble`, only the table is transformed.
+    pub fn faure_tezuka_scramble(&self, seed: u64) -> Self {
+        self.faure_tezuka_scramble_from(&mut SplitMix64::new(seed))
+    }
+
+    /// Same as `faure_tezuka_scramble` but drawing the scrambling matrix from the given source
+    pub fn faure_tezuka_scramble_from(&self, rng: &mut impl RandomSource) -> Self {
+        // Column `j` of the matrix selects index digit `j` itself and a random subset of the
+        // less significant digits, whose direction values are folded into that of digit `j`
+        let columns: Vec<T::IT> = (0..self.resolution)
+            .map(|j| {
+                let below = !(T::IT::max_value() << j);
+                (rng.next_word::<T::IT>() & below) | (T::IT::one() << j)
+            })
+            .collect();
+        let mut table = self.clone();
+        for (j, column) in columns.iter().enumerate() {
+            let row = &mut table.vals[j * self.dims..(j + 1) * self.dims];
+            row.fill(T::IT::zero());
+            for k in 0..=j {
+                if (*column >> k) & T::IT::one() == T::IT::one() {
+                    for (v, d) in row.iter_mut().zip(self.row(k)) {
+                        *v ^= *d;
+                    }
+                }
+            }
+        }
+        table
+    }
+
+    /// Initializes per-dimension direction values given sequence parameters
+    pub(crate) fn init_direction_vals<P, Param: SobolParams<P>>(
+        dims: usize,
+        resolution: usize,
+        params: &Param,
+    ) -> Vec<Vec<T::IT>>
+    where
+        T::IT: LossyFrom<P>,
+    {
+        (1..=dims)
+            .map(|dim| Self::init_dim_direction_vals(dim, resolution, params))
+            .collect()
+    }
+
+    /// Initializes the direction values of a single (one-based) dimension
+    fn init_dim_direction_vals<P, Param: SobolParams<P>>(
+        dim: usize,
+        resolution: usize,
+        params: &Param,
+    ) -> Vec<T::IT>
+    where
+        T::IT: LossyFrom<P>,
+    {
+        let bits = T::IT::BITS;
+
+        match dim {
+            1 => (1..=resolution)
+                .map(|i| T::IT::one() << (bits - i))
+                .collect(),
+            _ => {
+                // Import the parameters needed to prepare this dimension's direction vector
+                let p = params.get_dim(dim);
+                let s = if resolution >= p.s() {
+                    p.s()
+                } else {
+                    resolution
+                };
+
+                // Shift initial directions
+                let mut dirs: Vec<T::IT> = vec![T::IT::zero(); resolution];
+                for i in 1..=s {
+                    let m = T::IT::lossy_from(p.m(i - 1));
+                    dirs[i - 1] = m << (bits - i);
+                }
+
+                // Compute remaining directions
+                for i in s + 1..=resolution {
+                    dirs[i - 1] = dirs[i - s - 1] ^ (dirs[i - s - 1] >> s);
+
+                    for k in 1..s {
+                        let a = T::IT::lossy_from(p.coefficient(s - k - 1));
+                        let dir = dirs[i - k - 1];
+                        dirs[i - 1] ^= a * dir;
+                    }
+                }
+
+                dirs
+            }
+        }
+    }
+}
+
+impl<T: SobolType> Clone for DirectionTable<T> {
+    fn clone(&self) -> Self {
+        DirectionTable {
+            dims: self.dims,
+            resolution: self.resolution,
+            vals: self.vals.clone(),
+        }
+    }
+}
@@ -1,5 +1,6 @@
 use crate::{
-    GaussianRender, InternalType, LossyFrom, MultiDimGaussianRender, Render, SobolType, UnitRender,
+    ExactLenType, GaussianRender, InternalType, LossyFrom, MultiDimGaussianRender, Render,
+    SobolType, UnitRender,
 };
 use statrs::distribution::ContinuousCDF as _;
 
@@ -179,6 +180,13 @@ impl InternalType for u64 {
     const BITS: usize = 64;
 }
 
+impl ExactLenType for u8 {}
+impl ExactLenType for u16 {}
+#[cfg(any(target_pointer_width = "32", target_pointer_width = "64"))]
+impl ExactLenType for u32 {}
+#[cfg(target_pointer_width = "64")]
+impl ExactLenType for u64 {}
+
 /// InternalType implementation for 128-bit values
 impl InternalType for u128 {
     const BITS: usize = 128;
@@ -0,0 +1,30 @@
+/// Checks that the first `b^m` points of the given dimensions form a (t,m,s)-net in base `b`:
+/// every elementary box of volume `b^(t-m)` holds exactly `b^t` points. Values are read as
+/// fractions of the full width of `T`, possibly rounded down from values in base `b`.
+pub fn assert_net<T: Copy + Into<u128>>(points: &[Vec<T>], b: u32, dims: &[usize], t: u32, m: u32) {
+    let (b, bits) = (b as u128, 8 * size_of::<T>() as u32);
+    let mut shape = vec![0u32; dims.len()];
+    loop {
+        if shape.iter().sum::<u32>() == m - t {
+            let mut counts = vec![0u32; b.pow(m - t) as usize];
+            for p in &points[..b.pow(m) as usize] {
+                let cell = dims.iter().zip(&shape).fold(0, |cell, (d, a)| {
+                    // Values are rounded down, so nudge them up to land in the right interval
+                    let scale = b.pow(*a);
+                    cell * scale + (((p[*d].into() + 1) * scale - 1) >> bits)
+                });
+                counts[cell as usize] += 1;
+            }
+            assert!(
+                counts.iter().all(|c| *c == b.pow(t) as u32),
+                "{dims:?}: {shape:?}"
+            );
+        }
+        // Next shape in lexicographic order with each digit count at most m - t
+        let Some(i) = shape.iter().rposition(|a| *a < m - t) else {
+            break;
+        };
+        shape[i] += 1;
+        shape[i + 1..].iter_mut().for_each(|a| *a = 0);
+    }
+}
@@ -0,0 +1,135 @@
+mod common;
+
+use common::assert_net;
+use sobol_qmc::params::JoeKuoD6;
+use sobol_qmc::statrs::distribution::Normal;
+use sobol_qmc::*;
+
+/// Faure points in base 3, where dimension `j` uses the `j`-th power of the Pascal matrix
+#[test]
+fn test_faure_values() {
+    let faure = BaseBSequence::<f64>::faure(3);
+    assert_eq!(faure.base(), 3);
+    let expected = [
+        [0.0, 0.0, 0.0],
+        [1.0 / 3.0, 1.0 / 3.0, 1.0 / 3.0],
+        [2.0 / 3.0, 2.0 / 3.0, 2.0 / 3.0],
+        [1.0 / 9.0, 4.0 / 9.0, 7.0 / 9.0],
+        [4.0 / 9.0, 7.0 / 9.0, 1.0 / 9.0],
+    ];
+    for (point, expected) in faure.take(5).zip(expected) {
+        for (v, e) in point.iter().zip(expected) {
+            assert!(
+                (v - e).abs() < 1e-15 && *v <= e,
+                "{point:?} != {expected:?}"
+            );
+        }
+    }
+
+    // The base is the smallest prime at least as large as the number of dimensions
+    assert_eq!(BaseBSequence::<f64>::faure(1).base(), 2);
+    assert_eq!(BaseBSequence::<f64>::faure(8).base(), 11);
+    assert_eq!(BaseBSequence::<f64>::faure(11).base(), 11);
+}
+
+/// The first `b^m` points form a (0,m,s)-net: every elementary interval of volume `b^-m` holds
+/// exactly one point
+#[test]
+fn test_faure_net() {
+    let faure = BaseBSequence::<u64>::faure(3);
+    let b = faure.base();
+    let points: Vec<_> = faure.take(b.pow(4) as usize).collect();
+    assert_net(&points, b, &[0, 1, 2], 0, 4);
+}
+
+/// Generic matrices: the identity in base 3 is the radical inverse
+#[test]
+fn test_base_b_matrices() {
+    let m = BaseBSequence::<u32>::base_digits(3);
+    assert_eq!(m, 41);
+    let identity: Vec<Vec<u32>> = (0..m)
+        .map(|r| (0..m).map(|k| (r == k) as u32).collect())
+        .collect();
+    let seq = BaseBSequence::<u32>::from_matrices(3, &[identity], None, UnitRender).unwrap();
+    let halton = Halton::<u32>::new(2);
+    for index in [0, 1, 5, 1000, 123_456_789, u64::MAX - 1] {
+        assert_eq!(
+            seq.point_at(index).unwrap()[0],
+            halton.point_at(index).unwrap()[1]
+        );
+    }
+
+    // Faure in base 2 is the two-dimensional Sobol sequence, up to the order of the points
+    let faure = BaseBSequence::<u64>::faure(2);
+    let sobol = Sobol::<u64>::new(2, &JoeKuoD6::STANDARD).unwrap();
+    let mut faure: Vec<_> = faure.take(64).collect();
+    let mut sobol: Vec<_> = sobol.take(64).collect();
+    faure.sort();
+    sobol.sort();
+    assert_eq!(faure, sobol);
+
+    assert!(matches!(
+        BaseBSequence::<f64>::from_matrices(1, &[vec![vec![1]]], None, UnitRender),
+        Err(SobolError::InvalidBase { base: 1 })
+    ));
+    assert!(matches!(
+        BaseBSequence::<f64>::from_matrices(5, &[vec![vec![1; 28]; 27]], None, UnitRender),
+        Err(SobolError::DigitColumns {
+            dim: 0,
+            columns: 27,
+            digits: 28
+        })
+    ));
+    let mut short_column = vec![vec![1; 28]; 28];
+    short_column[3].pop();
+    assert!(matches!(
+        BaseBSequence::<f64>::from_matrices(
+            5,
+            &[vec![vec![1; 28]; 28], short_column],
+            None,
+            UnitRender
+        ),
+        Err(SobolError::DigitRows {
+            dim: 1,
+            column: 3,
+            rows: 27,
+            digits: 28
+        })
+    ));
+}
+
+#[test]
+fn test_faure_iteration() {
+    let mut faure = BaseBSequence::<f32>::faure(7);
+    let points: Vec<_> = faure.clone().take(500).collect();
+    assert!(points.iter().flatten().all(|v| (0.0..1.0).contains(v)));
+    for (i, point) in points.iter().enumerate() {
+        assert_eq!(faure.point_at(i as u64).as_ref(), Some(point));
+    }
+    assert_eq!(faure.clone().nth(321).as_ref(), points.get(321));
+    assert_eq!((faure.dims(), faure.position()), (7, 0));
+    faure.seek(100);
+    assert_eq!(faure.next().as_ref(), points.get(100));
+    assert_eq!(faure.position(), 101);
+    let mut buf = [0.0; 7];
+    assert!(faure.next_into(&mut buf));
+    assert_eq!(buf[..], points[101]);
+    assert!(faure.point_into(7, &mut buf));
+    assert_eq!(buf[..], points[7]);
+
+    // Indices are 64-bit and the last one is never reached
+    assert_eq!(faure.point_at(u64::MAX), None);
+    assert!(!faure.point_at_into(u64::MAX, &mut buf));
+    assert_eq!(buf[..], points[7]);
+    faure.seek(u64::MAX);
+    assert!(!faure.next_into(&mut buf));
+    assert_eq!(faure.next(), None);
+
+    let normal = GaussianRender(Normal::new(0.0, 1.0).unwrap());
+    let gaussian = BaseBSequence::<f64, _>::new_faure(4, None, normal).unwrap();
+    // The first point sits at the origin, i.e. at negative infinity
+    let mean = gaussian.skip(1).take(1000).flatten().sum::<f64>() / 4000.0;
+    assert!(mean.abs() < 0.05, "{mean}");
+    let wide = BaseBSequence::<u128>::faure(2);
+    assert_eq!(wide.point_at(1), Some(vec![1 << 127; 2]));
+}
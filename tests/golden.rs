@@ -0,0 +1,63 @@
+include!(concat!(env!("OUT_DIR"), "/gen_golden.rs"));
+
+use sobol_qmc::params::JoeKuoD6;
+use sobol_qmc::*;
+
+/// Regenerates every golden point of the given type and scheme and compares its integer
+/// values (IEEE 754 bits for floats) to those recorded in `tests/data/scramble_*.tsv.gz`
+fn validate_golden<T: SobolType>(
+    golden: &[(&str, usize, u64, u64, &[u128])],
+    scheme: ScrambleScheme,
+    name: &str,
+    bits: impl Fn(&T) -> u128,
+) where
+    T::IT: LossyFrom<u32> + TryFrom<u64>,
+    UnitRender: Render<T>,
+{
+    let params = JoeKuoD6::STANDARD;
+    let rows: Vec<_> = golden.iter().filter(|row| row.0 == name).collect();
+    assert!(!rows.is_empty(), "no golden points for {name}");
+    for &&(_, res, seed, index, expected) in &rows {
+        let resolution = (res > 0).then_some(res);
+        let sobol =
+            Sobol::<T>::new_with_resolution(expected.len(), &params, resolution, UnitRender)
+                .unwrap()
+                .with_scramble(scheme, seed);
+        let Ok(i) = T::IT::try_from(index) else {
+            panic!("index {index} out of range for {name}")
+        };
+        let point: Vec<_> = sobol.point_at(i).unwrap().iter().map(&bits).collect();
+        assert_eq!(
+            point, expected,
+            "{name} point #{index} (resolution {res}, seed {seed}) differs from golden vector"
+        );
+    }
+}
+
+#[test]
+fn golden_scramble_v1() {
+    let v1 = ScrambleScheme::V1;
+    validate_golden::<u16>(SCRAMBLE_V1, v1, "u16", |v| *v as u128);
+    validate_golden::<u32>(SCRAMBLE_V1, v1, "u32", |v| *v as u128);
+    validate_golden::<u64>(SCRAMBLE_V1, v1, "u64", |v| *v as u128);
+    validate_golden::<u128>(SCRAMBLE_V1, v1, "u128", |v| *v);
+    validate_golden::<f32>(SCRAMBLE_V1, v1, "f32", |v| v.to_bits() as u128);
+    validate_golden::<f64>(SCRAMBLE_V1, v1, "f64", |v| v.to_bits() as u128);
+}
+
+/// The sequential iterator reproduces the golden points as well as random access does
+#[test]
+fn golden_scramble_v1_iter() {
+    let params = JoeKuoD6::STANDARD;
+    let rows = SCRAMBLE_V1
+        .iter()
+        .filter(|row| row.0 == "u64" && row.1 == 0 && row.2 == 1);
+    let sobol = Sobol::<u64>::new(8, &params)
+        .unwrap()
+        .with_scramble(ScrambleScheme::V1, 1);
+    let points: Vec<_> = sobol.take(1 << 14).collect();
+    for &(_, _, _, index, expected) in rows {
+        let point: Vec<_> = points[index as usize].iter().map(|v| *v as u128).collect();
+        assert_eq!(point, expected);
+    }
+}
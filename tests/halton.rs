@@ -0,0 +1,116 @@
+use sobol_qmc::statrs::distribution::Normal;
+use sobol_qmc::*;
+
+/// The radical inverse in bases 2 and 3, starting from index zero
+#[test]
+fn test_halton_values() {
+    let halton = Halton::<f64>::new(2);
+    assert_eq!(halton.bases(), &[2, 3]);
+    let expected = [
+        [0.0, 0.0],
+        [0.5, 1.0 / 3.0],
+        [0.25, 2.0 / 3.0],
+        [0.75, 1.0 / 9.0],
+        [0.125, 4.0 / 9.0],
+    ];
+    for (point, expected) in halton.take(5).zip(expected) {
+        for (v, e) in point.iter().zip(expected) {
+            assert!(
+                (v - e).abs() < 1e-15 && *v <= e,
+                "{point:?} != {expected:?}"
+            );
+        }
+    }
+
+    // Integer values are the exact fixed-point radical inverse, rounded down
+    let halton = Halton::<u32>::new(3);
+    assert_eq!(
+        halton.point_at(1),
+        Some(vec![1 << 31, 1_431_655_765, 858_993_459])
+    );
+    assert_eq!(halton.point_at(0), Some(vec![0; 3]));
+    let wide = Halton::<u128>::new(2);
+    assert_eq!(wide.point_at(1), Some(vec![1 << 127, u128::MAX / 3]));
+    let bytes = Halton::<u8>::new(2);
+    assert_eq!(bytes.point_at(1), Some(vec![128, 85]));
+
+    // The last index is past the end, as for `next()`
+    assert_eq!(bytes.point_at(u64::MAX), None);
+    let mut buf = [7; 2];
+    assert!(!bytes.point_at_into(u64::MAX, &mut buf));
+    assert_eq!(buf, [7; 2]);
+    let mut last = bytes.clone();
+    last.seek(u64::MAX - 1);
+    assert!(last.next_into(&mut buf));
+    assert!(!last.next_into(&mut buf));
+    assert_eq!(last.next(), None);
+}
+
+/// The first `b^k` points of each dimension are the multiples of `b^-k` (rounded down to the
+/// fixed-point grid), with or without digit permutations
+#[test]
+fn test_halton_stratification() {
+    let variants = [
+        Halton::<u64>::new(6),
+        Halton::<u64>::new(6).with_faure_permutations(),
+        Halton::<u64>::new(6).with_random_permutations(11),
+    ];
+    for halton in variants {
+        for (dim, base) in halton.bases().iter().enumerate() {
+            let n = (*base as u64).pow(3);
+            let mut cells: Vec<_> = (0..n)
+                .map(|i| {
+                    ((halton.point_at(i).unwrap()[dim] as u128 * n as u128 + (1 << 63)) >> 64)
+                        as u64
+                })
+                .collect();
+            cells.sort();
+            assert!(cells.iter().copied().eq(0..n), "base {base}");
+        }
+    }
+}
+
+#[test]
+fn test_halton_permutations() {
+    let faure = Halton::<f64>::new(3).with_faure_permutations();
+    // Faure's permutation of base 5 is (0 3 2 1 4)
+    for (index, expected) in [(1, 0.6), (3, 0.2), (5, 0.12)] {
+        assert!((faure.point_at(index).unwrap()[2] - expected).abs() < 1e-15);
+    }
+    let plain = Halton::<f64>::new(3);
+    assert_eq!(
+        faure.point_at(2).unwrap()[..2],
+        plain.point_at(2).unwrap()[..2]
+    );
+
+    let random = Halton::<u32>::new(20).with_random_permutations(5);
+    let again = Halton::<u32>::new(20).with_random_permutations(5);
+    let other = Halton::<u32>::new(20).with_random_permutations(6);
+    let points: Vec<_> = random.take(100).collect();
+    assert_eq!(points, again.take(100).collect::<Vec<_>>());
+    assert_ne!(points, other.take(100).collect::<Vec<_>>());
+    assert_eq!(points[0], vec![0; 20]);
+}
+
+#[test]
+fn test_halton_iteration() {
+    let mut halton = Halton::<f32>::new(8);
+    let points: Vec<_> = halton.clone().take(500).collect();
+    assert!(points.iter().flatten().all(|v| (0.0..1.0).contains(v)));
+    assert_eq!(halton.clone().nth(321).as_ref(), points.get(321));
+    halton.seek(100);
+    assert_eq!(halton.next().as_ref(), points.get(100));
+    let mut buf = [0.0; 8];
+    assert!(LowDiscrepancy::next_into(&mut halton, &mut buf));
+    assert_eq!(buf[..], points[101]);
+    assert!(halton.point_into(7, &mut buf));
+    assert_eq!(buf[..], points[7]);
+
+    let normal = GaussianRender(Normal::new(0.0, 1.0).unwrap());
+    let gaussian = Halton::<f64, _>::new_with_resolution(4, None, normal).unwrap();
+    // The first point sits at the origin, i.e. at negative infinity
+    let mean = gaussian.skip(1).take(1000).flatten().sum::<f64>() / 4000.0;
+    assert!(mean.abs() < 0.05, "{mean}");
+    let ints = Halton::<i32>::new(2);
+    assert_eq!(ints.point_at(1).unwrap()[0], 0);
+}
@@ -0,0 +1,131 @@
+#[cfg(feature = "niederreiter-xing")]
+mod common;
+
+use sobol_qmc::params::GeneratorMatrices;
+use sobol_qmc::*;
+
+/// Formats generator matrices in the text format read by `GeneratorMatrices::parse`
+fn format(matrices: &[Vec<u32>], precision: usize) -> String {
+    let mut text = format!(
+        "# {} matrices\n{} {} {precision}\n",
+        matrices.len(),
+        matrices.len(),
+        matrices[0].len()
+    );
+    for m in matrices {
+        let cols: Vec<_> = m
+            .iter()
+            .map(|c| (c >> (32 - precision)).to_string())
+            .collect();
+        text += &cols.join(" ");
+        text += "\n";
+    }
+    text
+}
+
+#[test]
+fn test_parse_matrices() {
+    let matrices = GeneratorMatrices::parse("# identity\n2 4 4\n8 4 2 1\n8 4 2 1\n").unwrap();
+    assert_eq!(
+        (matrices.dims, matrices.columns, matrices.precision),
+        (2, 4, 4)
+    );
+    assert_eq!(matrices.matrix(1), &[8, 4, 2, 1]);
+    assert_eq!(
+        matrices.to_columns::<u8>(2)[0],
+        vec![0x80, 0x40, 0x20, 0x10]
+    );
+    assert_eq!(matrices.to_columns::<u8>(1).len(), 1);
+
+    let seq = Sobol::<u8>::from_generator_matrices(&matrices, 2, None, UnitRender)
+        .unwrap()
+        .with_ordering(PointOrder::Natural);
+    assert_eq!(seq.resolution, 4);
+    let points: Vec<_> = seq.collect();
+    assert_eq!(points.len(), 15);
+    assert_eq!(
+        points[..4],
+        [[0, 0], [0x80, 0x80], [0x40, 0x40], [0xc0, 0xc0]]
+    );
+
+    let Err(SobolError::MaxDim {
+        dims: 3,
+        max_dims: 2,
+    }) = Sobol::<u8>::from_generator_matrices(&matrices, 3, None, UnitRender)
+    else {
+        panic!("too many dimensions accepted");
+    };
+    let Err(SobolError::MatrixSize { .. }) =
+        Sobol::<u8>::from_generator_matrices(&matrices, 2, Some(8), UnitRender)
+    else {
+        panic!("resolution beyond the matrices accepted");
+    };
+}
+
+#[test]
+fn test_parse_errors() {
+    for (text, line) in [
+        ("2 4 4\n8 4 2 1\n8 4 2\n", 4),
+        ("2 4 4\n8 4 2 1\n8 4 2 1 0\n", 3),
+        ("2 4 4\n8 4 2 1\n8 4 x 1\n", 3),
+        ("# header\n1 2 4\n16 1\n", 3),
+        ("1 1 0\n1\n", 1),
+        ("1 1 65\n1\n", 1),
+        ("", 1),
+        ("18446744073709551615 2 8\n1\n", 1),
+        ("# header\n2 9223372036854775807 8\n1\n", 2),
+        ("2 18446744073709551616 8\n1\n", 1),
+    ] {
+        assert!(
+            matches!(GeneratorMatrices::parse(text), Err(SobolError::MatrixFormat { line: l }) if l == line),
+            "{text:?}: {:?}",
+            GeneratorMatrices::parse(text)
+        );
+    }
+}
+
+/// Matrices written out and read back generate the same sequence
+#[test]
+fn test_matrices_round_trip() {
+    let table = DirectionTable::<u32>::niederreiter(5, Some(24));
+    let matrices: Vec<Vec<u32>> = (0..5)
+        .map(|d| (0..24).map(|bit| table.get(bit, d)).collect())
+        .collect();
+    let parsed = GeneratorMatrices::parse(&format(&matrices, 24)).unwrap();
+    let loaded = Sobol::<u32>::from_generator_matrices(&parsed, 5, None, UnitRender).unwrap();
+    let computed = Sobol::<u32>::new_niederreiter(5, Some(24), UnitRender).unwrap();
+    assert!(loaded.zip(computed).take(1 << 12).all(|(a, b)| a == b));
+
+    // Wider types keep the digits in the most significant bits, narrower ones drop the rest
+    let wide = Sobol::<u64>::from_generator_matrices(&parsed, 5, None, UnitRender).unwrap();
+    let narrow = Sobol::<u16>::from_generator_matrices(&parsed, 5, None, UnitRender).unwrap();
+    let base = Sobol::<u32>::from_generator_matrices(&parsed, 5, None, UnitRender).unwrap();
+    assert!(base.zip(wide.zip(narrow)).take(1 << 12).all(|(b, (w, n))| {
+        (0..5).all(|d| w[d] >> 32 == b[d] as u64 && n[d] == (b[d] >> 16) as u16)
+    }));
+}
+
+/// The t-values of the vendored Niederreiter-Xing tables, by number of dimensions
+#[cfg(feature = "niederreiter-xing")]
+const NX_T_VALUES: &[(usize, u32)] = &[];
+
+/// Every embedded table has a recorded t-value, and its leading points form nets accordingly
+#[cfg(feature = "niederreiter-xing")]
+#[test]
+fn test_niederreiter_xing_tables() {
+    for table in GeneratorMatrices::niederreiter_xing_tables() {
+        let (_, t) = NX_T_VALUES
+            .iter()
+            .find(|(dims, _)| *dims == table.dims)
+            .unwrap_or_else(|| panic!("no t-value recorded for {} dimensions", table.dims));
+        assert!(
+            GeneratorMatrices::niederreiter_xing(table.dims).is_some_and(|m| m.dims == table.dims)
+        );
+        let seq =
+            Sobol::<u64>::from_generator_matrices(table, table.dims, None, UnitRender).unwrap();
+        let m = (t + 2).min(table.columns as u32);
+        let points: Vec<_> = seq.take(1 << m).collect();
+        let dims: Vec<_> = (0..table.dims).collect();
+        common::assert_net(&points, 2, &dims, *t, m);
+    }
+}
@@ -0,0 +1,81 @@
+mod common;
+
+use common::assert_net;
+use sobol_qmc::params::JoeKuoD6;
+use sobol_qmc::*;
+
+#[test]
+fn test_niederreiter_net() {
+    let seq = Sobol::<u32>::new_niederreiter(6, None, UnitRender).unwrap();
+    let points: Vec<_> = seq.take(1 << 10).collect();
+    // Polynomials x, x + 1, x^2 + x + 1, x^3 + x + 1, x^3 + x^2 + 1, x^4 + x + 1
+    assert_net(&points, 2, &[0, 1], 0, 10);
+    assert_net(&points, 2, &[0, 1, 2], 1, 10);
+    assert_net(&points, 2, &[0, 1, 2, 3], 3, 10);
+    assert_net(&points, 2, &[2, 3, 4], 4, 9);
+    assert_net(&points, 2, &[0, 1, 2, 3, 4, 5], 8, 10);
+}
+
+#[test]
+fn test_niederreiter_matches_sobol() {
+    let params = JoeKuoD6::STANDARD;
+    let sobol = Sobol::<f64>::new(2, &params).unwrap();
+    let niederreiter = Sobol::<f64>::new_niederreiter(2, None, UnitRender).unwrap();
+    assert!(sobol.zip(niederreiter).take(1 << 12).all(|(a, b)| a == b));
+
+    // Later dimensions differ, yet every point stays in the unit interval
+    let niederreiter = Sobol::<f64>::new_niederreiter(12, None, UnitRender).unwrap();
+    let sobol = Sobol::<f64>::new(12, &params).unwrap();
+    let points: Vec<_> = niederreiter.clone().take(1 << 12).collect();
+    assert!(points.iter().flatten().all(|v| (0.0..1.0).contains(v)));
+    let differ = |d: usize| sobol.clone().zip(&points).any(|(a, b)| a[d] != b[d]);
+    assert_eq!((0..12).filter(|d| differ(*d)).count(), 9);
+
+    // Random access, scrambling and other orderings come with the shared machinery
+    assert_eq!(niederreiter.point_at(1000).as_ref(), points.get(1000));
+    let natural = niederreiter.clone().with_ordering(PointOrder::Natural);
+    assert_eq!(
+        natural.point_at_natural(7),
+        niederreiter.point_at_natural(7)
+    );
+    let scrambled = niederreiter.with_owen_scramble(3);
+    assert!(scrambled.take(1 << 12).zip(&points).any(|(a, b)| a != *b));
+}
+
+#[test]
+fn test_from_matrices() {
+    // The identity matrix yields the van der Corput sequence in every dimension
+    let identity: Vec<u16> = (0..16).map(|r| 1 << (15 - r)).collect();
+    let seq = Sobol::<u16>::from_matrices(&[identity.clone(), identity.clone()], None, UnitRender)
+        .unwrap()
+        .with_ordering(PointOrder::Natural);
+    let points: Vec<_> = seq.take(4).collect();
+    assert_eq!(
+        points,
+        [
+            [0, 0],
+            [1 << 15, 1 << 15],
+            [1 << 14, 1 << 14],
+            [3 << 14, 3 << 14]
+        ]
+    );
+
+    // Columns are masked to the resolution, which each matrix must cover
+    let low = Sobol::<u16>::from_matrices(&[vec![u16::MAX; 4]], Some(4), UnitRender).unwrap();
+    assert_eq!(low.point_at(1), Some(vec![0xf000]));
+    let Err(SobolError::MatrixSize {
+        dim: 1,
+        columns: 8,
+        resolution: 16,
+    }) = Sobol::<u16>::from_matrices(&[identity, vec![0; 8]], None, UnitRender)
+    else {
+        panic!("short matrix accepted");
+    };
+
+    let table = DirectionTable::<u32>::niederreiter(4, Some(20));
+    let matrices: Vec<Vec<u32>> = (0..4)
+        .map(|d| (0..20).map(|bit| table.get(bit, d)).collect())
+        .collect();
+    let rebuilt = DirectionTable::<u32>::from_matrices(&matrices, Some(20)).unwrap();
+    assert!((0..20).all(|bit| rebuilt.row(bit) == table.row(bit)));
+}
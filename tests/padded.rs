@@ -0,0 +1,90 @@
+mod common;
+
+use common::assert_net;
+use sobol_qmc::params::JoeKuoD6;
+use sobol_qmc::statrs::distribution::Normal;
+use sobol_qmc::*;
+use std::sync::Arc;
+
+/// The number of dimensions of the sequences under test
+const DIMS: usize = 9;
+
+/// log2 of the number of points compared per test
+const M: u32 = 10;
+
+/// Every dimension pair drawn from the same group is a scrambled (0,2)-sequence
+#[test]
+fn test_padded_pairs() {
+    let params = JoeKuoD6::STANDARD;
+    let padded = PaddedSobol::<u32>::new(DIMS, 2, &params, 17).unwrap();
+    assert_eq!(padded.group_dims(), 2);
+    let points: Vec<_> = padded.clone().take(1 << M).collect();
+    for g in 0..DIMS / 2 {
+        assert_net(&points, 2, &[2 * g, 2 * g + 1], 0, M);
+    }
+    // The odd dimension out still forms a group of its own, stratified in one dimension
+    assert_net(&points, 2, &[DIMS - 1], 0, M);
+
+    // Groups are shuffled independently, so corresponding dimensions differ
+    assert!(points.iter().any(|p| p[0] != p[2]));
+    assert!(points.iter().any(|p| p[1] != p[3]));
+}
+
+#[test]
+fn test_padded_access() {
+    let params = JoeKuoD6::STANDARD;
+    let padded = PaddedSobol::<u32>::new(DIMS, 4, &params, 3).unwrap();
+    let points: Vec<_> = padded.clone().take(1 << M).collect();
+    assert_eq!(points, padded.clone().take(1 << M).collect::<Vec<_>>());
+    for (i, p) in points.iter().enumerate().step_by(37) {
+        assert_eq!(padded.point_at(i as u32).as_ref(), Some(p));
+        let mut buf = vec![0; DIMS];
+        assert!(padded.point_at_into(i as u32, &mut buf));
+        assert_eq!(&buf, p);
+        assert_eq!(padded.clone().nth(i).as_ref(), Some(p));
+    }
+    let mut seeked = padded.clone();
+    seeked.seek(100);
+    assert_eq!(seeked.next().as_ref(), points.get(100));
+    assert_eq!(padded.len(), u32::MAX as usize);
+
+    let other = PaddedSobol::<u32>::new(DIMS, 4, &params, 4).unwrap();
+    assert!(other.zip(points).any(|(a, b)| a != b));
+}
+
+/// The groups share their table, and any render may be used
+#[test]
+fn test_padded_render() {
+    let params = JoeKuoD6::STANDARD;
+    let table = DirectionTable::<f64>::cached(2, &params, None).unwrap();
+    let uniform = PaddedSobol::from_table(table.clone(), DIMS, 5, UnitRender).unwrap();
+    assert!(Arc::ptr_eq(uniform.table(), &table));
+    assert!(
+        uniform
+            .take(1 << M)
+            .flatten()
+            .all(|v| (0.0..1.0).contains(&v))
+    );
+
+    let normal = GaussianRender(Normal::new(0.0, 1.0).unwrap());
+    let gaussian = PaddedSobol::from_table(table, DIMS, 5, normal).unwrap();
+    let mean = gaussian.take(1 << M).flatten().sum::<f64>() / (DIMS << M) as f64;
+    assert!(mean.abs() < 0.05, "{mean}");
+}
+
+/// Seeds may come from any random source, and groups must not be empty
+#[test]
+fn test_padded_from() {
+    let params = JoeKuoD6::STANDARD;
+    let table = DirectionTable::<f64>::cached(2, &params, None).unwrap();
+    let seeded = PaddedSobol::from_table(table.clone(), DIMS, 5, UnitRender).unwrap();
+    let mut rng = SplitMix64::new(5);
+    let from = PaddedSobol::from_table_from(table, DIMS, &mut rng, UnitRender).unwrap();
+    assert!(seeded.take(64).eq(from.take(64)));
+
+    let empty = Arc::new(DirectionTable::<f64>::new(0, &params, None).unwrap());
+    assert!(matches!(
+        PaddedSobol::from_table(empty, DIMS, 5, UnitRender),
+        Err(SobolError::GroupDims { group_dims: 0 })
+    ));
+}
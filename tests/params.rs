@@ -22,7 +22,7 @@ fn test_direction_vals() {
     let dir_vals = Sobol::<u32>::init_direction_vals(6, 32, &params);
 
     // Reference direction values for a 32-bit sequence with 6 dimensions
-    let ref_dir_vals = vec![
+    let ref_dir_vals = [
         vec![
             2147483648, 1073741824, 536870912, 268435456, 134217728, 67108864, 33554432, 16777216,
             8388608, 4194304, 2097152, 1048576, 524288, 262144, 131072, 65536, 32768, 16384, 8192,
@@ -73,3 +73,17 @@ fn test_direction_vals() {
     assert!(dir_vals[4] == ref_dir_vals[4]);
     assert!(dir_vals[5] == ref_dir_vals[5]);
 }
+
+/// Builds a direction table and checks it holds the transposed direction values
+#[test]
+fn test_direction_table() {
+    let params = JoeKuoD6::STANDARD;
+    let dir_vals = Sobol::<u16>::init_direction_vals(40, 12, &params);
+    let table = DirectionTable::<u16>::new(40, &params, Some(12)).unwrap();
+    assert_eq!((table.dims(), table.resolution()), (40, 12));
+    for bit in 0..12 {
+        let col: Vec<_> = dir_vals.iter().map(|d| d[bit]).collect();
+        assert!(table.row(bit) == col);
+        assert!((0..40).all(|dim| table.get(bit, dim) == dir_vals[dim][bit]));
+    }
+}
@@ -0,0 +1,37 @@
+#![cfg(feature = "rayon")]
+
+use rayon::prelude::*;
+use sobol_qmc::params::JoeKuoD6;
+use sobol_qmc::*;
+
+/// Parallel iteration yields the same points, in the same order, as serial iteration
+#[test]
+fn test_par_iter_order() {
+    let sobol = Sobol::<u32>::new(16, &JoeKuoD6::STANDARD)
+        .unwrap()
+        .range(17..10_017);
+    let serial: Vec<_> = sobol.clone().collect();
+    let parallel: Vec<_> = sobol.into_par_iter().collect();
+    assert_eq!(serial, parallel);
+}
+
+/// Parallel reductions over a range of the sequence match their serial counterparts
+#[test]
+fn test_par_iter_sum() {
+    let sobol = Sobol::<f64>::new(8, &JoeKuoD6::STANDARD)
+        .unwrap()
+        .range(0..1 << 14);
+    let f = |p: Vec<f64>| p.iter().map(|x| (x * 8.0).floor() as u64).sum::<u64>();
+    let serial: u64 = sobol.clone().map(f).sum();
+    let parallel: u64 = sobol.par_iter().map(f).sum();
+    assert_eq!(serial, parallel);
+}
+
+/// Sequences longer than `usize::MAX` can still be driven in parallel
+#[test]
+fn test_par_iter_unbounded() {
+    let sobol = Sobol::<u128>::new(2, &JoeKuoD6::STANDARD).unwrap();
+    assert_eq!(sobol.par_iter().opt_len(), None);
+    let first = sobol.par_iter().find_first(|p| p[0] > 3 << 125);
+    assert_eq!(first, sobol.clone().find(|p| p[0] > 3 << 125));
+}
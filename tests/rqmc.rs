@@ -0,0 +1,81 @@
+use sobol_qmc::params::JoeKuoD6;
+use sobol_qmc::{Randomization, Rqmc, Sobol};
+
+const DIMS: usize = 6;
+
+/// Integrand with known mean 1 over the unit cube
+fn product(x: &[f64]) -> f64 {
+    x.iter().map(|v| 1.5 * v.sqrt()).product()
+}
+
+#[test]
+fn test_rqmc_estimate() {
+    let params = JoeKuoD6::STANDARD;
+    let base = Sobol::<f64>::new(DIMS, &params).unwrap();
+    for randomization in [
+        Randomization::DigitalShift,
+        Randomization::RandomRotation,
+        Randomization::LinearScramble,
+        Randomization::FullLinearScramble,
+        Randomization::OwenScramble,
+    ] {
+        let est = Rqmc::new(base.clone(), randomization)
+            .with_replicates(32)
+            .with_points(1 << 12)
+            .with_seed(5)
+            .run(product);
+        assert_eq!(est.replicates.len(), 32);
+        assert!(est.std_error > 0.0 && est.std_error < 1e-2, "{est:?}");
+        assert!(est.interval.0 < est.mean && est.mean < est.interval.1);
+        assert!(
+            (est.mean - 1.0).abs() < 5.0 * est.std_error,
+            "{randomization:?}: {est:?}"
+        );
+    }
+}
+
+#[test]
+fn test_rqmc_seeds() {
+    let params = JoeKuoD6::STANDARD;
+    let rqmc = Rqmc::new(
+        Sobol::<f64>::new(DIMS, &params).unwrap(),
+        Randomization::default(),
+    )
+    .with_replicates(8)
+    .with_points(256);
+    let seeds = rqmc.replicate_seeds();
+    let mut distinct = seeds.clone();
+    distinct.sort();
+    distinct.dedup();
+    assert_eq!(distinct.len(), seeds.len());
+    assert_ne!(rqmc.clone().with_seed(1).replicate_seeds(), seeds);
+
+    // Runs are reproducible and wider intervals come with higher confidence
+    let a = rqmc.run(product);
+    assert_eq!(a, rqmc.run(product));
+    let b = rqmc.clone().with_confidence(0.99).run(product);
+    assert_eq!(a.mean, b.mean);
+    assert!(b.interval.1 - b.interval.0 > a.interval.1 - a.interval.0);
+}
+
+#[test]
+#[should_panic(expected = "at least two replicates are required")]
+fn test_rqmc_replicates() {
+    let params = JoeKuoD6::STANDARD;
+    let _ = Rqmc::new(
+        Sobol::<f64>::new(DIMS, &params).unwrap(),
+        Randomization::default(),
+    )
+    .with_replicates(1);
+}
+
+#[test]
+#[should_panic(expected = "base sequence has no points left to evaluate")]
+fn test_rqmc_exhausted() {
+    let params = JoeKuoD6::STANDARD;
+    let mut base = Sobol::<f64>::new(DIMS, &params).unwrap().range(0..16);
+    base.seek(16);
+    let rqmc = Rqmc::new(base, Randomization::default());
+    assert_eq!((rqmc.replicates(), rqmc.points()), (16, 1024));
+    rqmc.run(product);
+}
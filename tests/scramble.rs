@@ -0,0 +1,387 @@
+mod common;
+
+use common::assert_net;
+use sobol_qmc::params::JoeKuoD6;
+use sobol_qmc::*;
+
+/// The number of dimensions of the sequences under test
+const DIMS: usize = 12;
+
+/// The number of points compared per test
+const N: usize = 1 << 10;
+
+/// A digital shift XORs each dimension with a constant word, preserving the net structure
+#[test]
+fn test_digital_shift() {
+    let params = JoeKuoD6::STANDARD;
+    let plain = Sobol::<u32>::new(DIMS, &params).unwrap();
+    let shifted = plain.clone().with_digital_shift(7);
+
+    let points: Vec<_> = shifted.clone().take(N).collect();
+    let shift: Vec<_> = points[0].clone();
+    assert!(shift.iter().any(|s| *s != 0));
+    for (p, q) in points.iter().zip(plain.clone()) {
+        let diff: Vec<_> = p.iter().zip(&q).map(|(a, b)| a ^ b).collect();
+        assert_eq!(diff, shift);
+    }
+
+    // Reproducible from the seed, preserved by clone and seek, and seed dependent
+    let again: Vec<_> = plain.clone().with_digital_shift(7).take(N).collect();
+    assert_eq!(points, again);
+    let mut seeked = shifted.clone();
+    seeked.seek(100);
+    assert_eq!(seeked.next().as_ref(), points.get(100));
+    assert_eq!(shifted.point_at(100).as_ref(), points.get(100));
+    assert_ne!(plain.with_digital_shift(8).next(), points.first().cloned());
+}
+
+/// Shifted floating point sequences stay within the unit interval at full resolution
+#[test]
+fn test_digital_shift_unit_range() {
+    let params = JoeKuoD6::STANDARD;
+    for seed in 0..16 {
+        let f32s = Sobol::<f32>::new(DIMS, &params)
+            .unwrap()
+            .with_digital_shift(seed);
+        assert!(f32s.take(N).flatten().all(|v| (0.0..1.0).contains(&v)));
+        let f64s = Sobol::<f64>::new(DIMS, &params)
+            .unwrap()
+            .with_digital_shift(seed);
+        assert!(f64s.take(N).flatten().all(|v| (0.0..1.0).contains(&v)));
+    }
+}
+
+/// Every dimension of the first 2^m shifted points still hits each elementary interval once
+#[test]
+fn test_digital_shift_stratification() {
+    let sobol = Sobol::<u128>::new(DIMS, &JoeKuoD6::STANDARD)
+        .unwrap()
+        .with_digital_shift(99);
+    let points: Vec<_> = sobol.take(N).collect();
+    for dim in 0..DIMS {
+        let mut cells: Vec<_> = points.iter().map(|p| p[dim] >> 118).collect();
+        cells.sort();
+        assert!(cells.iter().copied().eq(0..N as u128));
+    }
+}
+
+/// A random rotation adds a constant to each dimension modulo one
+#[test]
+fn test_random_rotation() {
+    let params = JoeKuoD6::STANDARD;
+    let plain = Sobol::<u64>::new(DIMS, &params).unwrap();
+    let rotated = plain.clone().with_random_rotation(3);
+    let points: Vec<_> = rotated.clone().take(N).collect();
+    let rotation = points[0].clone();
+    for (p, q) in points.iter().zip(plain.clone()) {
+        let diff: Vec<_> = p.iter().zip(&q).map(|(a, b)| a.wrapping_sub(*b)).collect();
+        assert_eq!(diff, rotation);
+    }
+    assert_eq!(rotated.point_at(999).as_ref(), points.get(999));
+    assert!(
+        points.iter().eq(plain
+            .with_random_rotation(3)
+            .take(N)
+            .collect::<Vec<_>>()
+            .iter())
+    );
+}
+
+/// Rotations are drawn from the grid of the resolution, so rotated values stay on the grid
+#[test]
+fn test_random_rotation_grid() {
+    let params = JoeKuoD6::STANDARD;
+    for seed in 0..16 {
+        let rotated = Sobol::<f64>::new_with_resolution(2, &params, Some(3), UnitRender)
+            .unwrap()
+            .with_random_rotation(seed);
+        let points: Vec<_> = rotated.collect();
+        assert_eq!(points.len(), 7);
+        assert!(points.iter().flatten().all(|v| (v * 8.0).fract() == 0.0));
+        // Each dimension visits distinct grid values, so it is uniform over the grid rather
+        // than over the unit interval
+        for dim in 0..2 {
+            let mut cells: Vec<_> = points.iter().map(|p| (p[dim] * 8.0) as u8).collect();
+            cells.sort();
+            cells.dedup();
+            assert_eq!(cells.len(), 7);
+        }
+    }
+}
+
+/// Rotated floats wrap around strictly inside the unit interval and propagate through
+/// Gaussian renders
+#[test]
+fn test_random_rotation_floats() {
+    let params = JoeKuoD6::STANDARD;
+    for seed in 0..16 {
+        let unit = Sobol::<f64>::new(DIMS, &params)
+            .unwrap()
+            .with_random_rotation(seed);
+        let points: Vec<_> = unit.clone().take(N).collect();
+        assert!(points.iter().flatten().all(|v| (0.0..1.0).contains(v)));
+
+        let plain = Sobol::<f64>::new(DIMS, &params).unwrap();
+        for (p, q) in points.iter().zip(plain).skip(1) {
+            for (dim, (a, b)) in p.iter().zip(&q).enumerate() {
+                assert!((a - (b + points[0][dim]) % 1.0).abs() < 1e-15);
+            }
+        }
+
+        let normal = statrs::distribution::Normal::standard();
+        let gauss = Sobol::<f64, GaussianRender>::new_with_resolution(
+            DIMS,
+            &params,
+            None,
+            GaussianRender(normal),
+        )
+        .unwrap()
+        .with_random_rotation(seed);
+        for (g, u) in gauss.zip(points).take(64) {
+            for (a, b) in g.iter().zip(&u) {
+                use statrs::distribution::ContinuousCDF;
+                assert_eq!(*a, normal.inverse_cdf(*b));
+            }
+        }
+
+        let f32s = Sobol::<f32>::new(DIMS, &params)
+            .unwrap()
+            .with_digital_shift(seed)
+            .with_random_rotation(seed);
+        assert!(f32s.take(N).flatten().all(|v| (0.0..1.0).contains(&v)));
+    }
+}
+
+/// Linear matrix scrambling preserves stratification of every dimension and is reproducible
+#[test]
+fn test_linear_scramble() {
+    let params = JoeKuoD6::STANDARD;
+    let plain = Sobol::<u32>::new(DIMS, &params).unwrap();
+    let scrambled = plain.clone().with_linear_scramble(5);
+    assert!(!std::sync::Arc::ptr_eq(plain.table(), scrambled.table()));
+
+    let points: Vec<_> = scrambled.clone().take(N).collect();
+    assert_ne!(points, plain.clone().take(N).collect::<Vec<_>>());
+    assert!(points[0].iter().all(|v| *v == 0));
+    for dim in 0..DIMS {
+        for m in [1, 4, 10] {
+            let mut cells: Vec<_> = points[..1 << m]
+                .iter()
+                .map(|p| p[dim] >> (32 - m))
+                .collect();
+            cells.sort();
+            assert!(cells.iter().copied().eq(0..1 << m));
+        }
+    }
+
+    // The leading digit of every dimension is left unchanged
+    for (p, q) in points.iter().zip(plain.clone()) {
+        assert!(p.iter().zip(&q).all(|(a, b)| a >> 31 == b >> 31));
+    }
+
+    let again: Vec<_> = plain.clone().with_linear_scramble(5).take(N).collect();
+    assert_eq!(points, again);
+    assert_eq!(scrambled.point_at(777).as_ref(), points.get(777));
+
+    // Scrambling mid-sequence keeps the position
+    let mut resumed = plain.clone();
+    resumed.nth(99);
+    let mut resumed = resumed.with_linear_scramble(5);
+    assert_eq!(resumed.position(), 100);
+    assert_eq!(resumed.next().as_ref(), points.get(100));
+}
+
+/// Linear matrix scrambling combines with a digital shift
+#[test]
+fn test_linear_scramble_with_shift() {
+    let sobol = Sobol::<f64>::new(DIMS, &JoeKuoD6::STANDARD)
+        .unwrap()
+        .with_linear_scramble(11)
+        .with_digital_shift(12);
+    let points: Vec<_> = sobol.take(N).collect();
+    assert!(points.iter().flatten().all(|v| (0.0..1.0).contains(v)));
+    for dim in 0..DIMS {
+        let mut cells: Vec<_> = points
+            .iter()
+            .map(|p| (p[dim] * N as f64) as usize)
+            .collect();
+        cells.sort();
+        assert!(cells.iter().copied().eq(0..N));
+    }
+}
+
+/// Owen scrambling keeps the first two dimensions a (0,2)-sequence and is reproducible
+#[test]
+fn test_owen_scramble() {
+    let params = JoeKuoD6::STANDARD;
+    let plain = Sobol::<u64>::new(DIMS, &params).unwrap();
+    let scrambled = plain.clone().with_owen_scramble(21);
+    let points: Vec<_> = scrambled.clone().take(N).collect();
+    assert_ne!(points, plain.clone().take(N).collect::<Vec<_>>());
+    assert_net(&points, 2, &[0, 1], 0, 10);
+    for dim in 0..DIMS {
+        assert_net(&points, 2, &[dim], 0, 10);
+    }
+
+    assert_eq!(scrambled.point_at(512).as_ref(), points.get(512));
+    let again: Vec<_> = plain.clone().with_owen_scramble(21).take(N).collect();
+    assert_eq!(points, again);
+
+    // Per-dimension seeds: dimensions sharing a seed are scrambled alike
+    let seeded = Sobol::<u64>::new_with_dims([2, 2], &params, None, UnitRender)
+        .unwrap()
+        .with_owen_scramble_seeds([4, 4]);
+    assert!(seeded.take(N).all(|p| p[0] == p[1]));
+    let partial = plain.clone().with_owen_scramble_seeds([4]);
+    assert!(partial.zip(plain).take(N).all(|(p, q)| p[1..] == q[1..]));
+}
+
+/// Owen scrambled sequences keep their resolution, so floats stay inside the unit interval
+#[test]
+fn test_owen_scramble_resolution() {
+    let params = JoeKuoD6::STANDARD;
+    for seed in 0..16 {
+        let f64s = Sobol::<f64>::new(DIMS, &params)
+            .unwrap()
+            .with_owen_scramble(seed);
+        assert!(f64s.take(N).flatten().all(|v| (0.0..1.0).contains(&v)));
+        let u8s = Sobol::<u8>::new_with_resolution(DIMS, &params, Some(5), UnitRender)
+            .unwrap()
+            .with_owen_scramble(seed);
+        assert!(u8s.flatten().all(|v| v & 0b111 == 0));
+        let i128s = Sobol::<i128>::new(DIMS, &params)
+            .unwrap()
+            .with_owen_scramble(seed);
+        assert_eq!(i128s.take(N).count(), N);
+    }
+}
+
+/// Counts up from a seed, standing in for an external generator
+struct Counter(u64);
+
+impl RandomSource for Counter {
+    fn next_u64(&mut self) -> u64 {
+        self.0 += 1;
+        self.0.wrapping_mul(0x9e37_79b9_7f4a_7c15)
+    }
+}
+
+/// Seeded randomizations draw from `SplitMix64`, and any other source can be plugged in
+#[test]
+fn test_random_source() {
+    let params = JoeKuoD6::STANDARD;
+    let plain = Sobol::<u32>::new(DIMS, &params).unwrap();
+    let pairs: [(Sobol<u32>, Sobol<u32>); 4] = [
+        (
+            plain.clone().with_digital_shift(3),
+            plain
+                .clone()
+                .with_digital_shift_from(&mut SplitMix64::new(3)),
+        ),
+        (
+            plain.clone().with_random_rotation(3),
+            plain
+                .clone()
+                .with_random_rotation_from(&mut SplitMix64::new(3)),
+        ),
+        (
+            plain.clone().with_linear_scramble(3),
+            plain
+                .clone()
+                .with_linear_scramble_from(&mut SplitMix64::new(3)),
+        ),
+        (
+            plain.clone().with_owen_scramble(3),
+            plain
+                .clone()
+                .with_owen_scramble_from(&mut SplitMix64::new(3)),
+        ),
+    ];
+    for (seeded, from) in pairs {
+        assert!(seeded.zip(from).take(N).all(|(a, b)| a == b));
+    }
+
+    let custom = plain.clone().with_owen_scramble_from(&mut Counter(0));
+    let again = plain.clone().with_owen_scramble_from(&mut Counter(0));
+    assert!(custom.clone().zip(again).take(N).all(|(a, b)| a == b));
+    assert!(custom.zip(plain).take(N).any(|(a, b)| a != b));
+}
+
+/// Any `rand_core::RngCore` can drive the randomization through `RngSource`
+#[cfg(feature = "rand_core")]
+#[test]
+fn test_rand_core_source() {
+    struct Rng(SplitMix64);
+
+    impl rand_core::RngCore for Rng {
+        fn next_u32(&mut self) -> u32 {
+            self.0.next_u64() as u32
+        }
+        fn next_u64(&mut self) -> u64 {
+            self.0.next_u64()
+        }
+        fn fill_bytes(&mut self, dst: &mut [u8]) {
+            rand_core::impls::fill_bytes_via_next(self, dst)
+        }
+    }
+
+    let params = JoeKuoD6::STANDARD;
+    let plain = Sobol::<f64>::new(DIMS, &params).unwrap();
+    let mut rng = Rng(SplitMix64::new(9));
+    let from = plain
+        .clone()
+        .with_linear_scramble_from(&mut RngSource(&mut rng));
+    let seeded = plain.clone().with_linear_scramble(9);
+    assert!(from.zip(seeded.clone()).take(N).all(|(a, b)| a == b));
+    // The borrowed generator carries on from where the randomization left off
+    let owned = plain.with_linear_scramble_from(&mut RngSource(rng));
+    assert!(owned.zip(seeded).take(N).any(|(a, b)| a != b));
+}
+
+/// Faure-Tezuka scrambling permutes the points within each block of `2^m` leading indices, and
+/// commutes with Matoušek scrambling
+#[test]
+fn test_faure_tezuka_scramble() {
+    let params = JoeKuoD6::STANDARD;
+    let plain = Sobol::<u32>::new(DIMS, &params).unwrap();
+    let scrambled = plain.clone().with_faure_tezuka_scramble(8);
+    let mut expected: Vec<_> = plain.clone().take(N).collect();
+    let mut points: Vec<_> = scrambled.clone().take(N).collect();
+    assert_ne!(points, expected);
+    for m in [1, 4, 10] {
+        let (mut a, mut b) = (expected[..1 << m].to_vec(), points[..1 << m].to_vec());
+        a.sort();
+        b.sort();
+        assert_eq!(a, b, "m = {m}");
+    }
+    points.sort();
+    expected.sort();
+    assert_eq!(points, expected);
+
+    let left_right = plain
+        .clone()
+        .with_linear_scramble(1)
+        .with_faure_tezuka_scramble(2);
+    let right_left = plain
+        .clone()
+        .with_faure_tezuka_scramble(2)
+        .with_linear_scramble(1);
+    assert!(
+        left_right
+            .clone()
+            .zip(right_left)
+            .take(N)
+            .all(|(a, b)| a == b)
+    );
+    assert!(
+        left_right
+            .zip(plain.with_linear_scramble(1))
+            .take(N)
+            .any(|(a, b)| a != b)
+    );
+
+    let wide = Sobol::<u128>::new(DIMS, &params)
+        .unwrap()
+        .with_faure_tezuka_scramble(8);
+    assert_eq!(wide.point_at(0), Some(vec![0; DIMS]));
+}
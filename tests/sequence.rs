@@ -0,0 +1,89 @@
+use sobol_qmc::params::JoeKuoD6;
+use sobol_qmc::*;
+
+/// The number of points compared per test
+const N: usize = 1 << 10;
+
+/// Estimates the mean of a function over the first `n` points of any generator
+fn mean<S: LowDiscrepancy<Item = f64>>(seq: &mut S, n: usize, f: impl Fn(&[f64]) -> f64) -> f64 {
+    let mut point = vec![0.0; seq.dims()];
+    let mut sum = 0.0;
+    for _ in 0..n {
+        assert!(seq.next_into(&mut point));
+        sum += f(&point);
+    }
+    sum / n as f64
+}
+
+/// Collects the points at the given indices of any random access generator
+fn points_at<S: RandomAccess<Item = u32>>(seq: &S, indices: &[u64]) -> Vec<Vec<u32>> {
+    indices
+        .iter()
+        .map(|i| {
+            let mut point = vec![0; seq.dims()];
+            assert!(seq.point_into(*i, &mut point));
+            point
+        })
+        .collect()
+}
+
+#[test]
+fn test_low_discrepancy() {
+    let params = JoeKuoD6::STANDARD;
+    let product = |x: &[f64]| x.iter().map(|v| 2.0 * v).product::<f64>();
+    let mut sobol = Sobol::<f64>::new(4, &params).unwrap();
+    let mut fixed = SobolN::<f64, 4>::new(&params).unwrap();
+    let mut padded = PaddedSobol::<f64>::new(4, 2, &params, 1).unwrap();
+    assert_eq!(LowDiscrepancy::dims(&fixed), 4);
+    let estimates = [
+        mean(&mut sobol, N, product),
+        mean(&mut fixed, N, product),
+        mean(&mut padded, N, product),
+    ];
+    assert_eq!(estimates[0], estimates[1]);
+    for estimate in estimates {
+        assert!((estimate - 1.0).abs() < 0.05, "{estimates:?}");
+    }
+    assert_eq!(sobol.remaining(), fixed.remaining());
+    assert_eq!(
+        padded.remaining(),
+        Some(((1 << 53) - 1 - N as u64) as usize)
+    );
+
+    let mut short = Sobol::<f64>::new(2, &params).unwrap().range(0..2);
+    let mut buf = [0.0; 2];
+    assert!(LowDiscrepancy::next_into(&mut short, &mut buf));
+    assert!(LowDiscrepancy::next_into(&mut short, &mut buf));
+    assert_eq!(short.remaining(), Some(0));
+    assert!(!LowDiscrepancy::next_into(&mut short, &mut buf));
+}
+
+#[test]
+fn test_random_access() {
+    let params = JoeKuoD6::STANDARD;
+    let indices = [0, 1, 5, 1000, u32::MAX as u64 - 1];
+    let sobol = Sobol::<u32>::new(3, &params).unwrap();
+    let fixed = SobolN::<u32, 3>::new(&params).unwrap();
+    let expected: Vec<_> = indices
+        .iter()
+        .map(|i| sobol.point_at(*i as u32).unwrap())
+        .collect();
+    assert_eq!(points_at(&sobol, &indices), expected);
+    assert_eq!(points_at(&fixed, &indices), expected);
+
+    let padded = PaddedSobol::<u32>::new(3, 3, &params, 9).unwrap();
+    let expected: Vec<_> = indices
+        .iter()
+        .map(|i| padded.point_at(*i as u32).unwrap())
+        .collect();
+    assert_eq!(points_at(&padded, &indices), expected);
+
+    // Indices beyond the end of the sequence (or its index type) yield no point
+    let mut buf = [7; 3];
+    for index in [u32::MAX as u64, 1 << 40] {
+        assert!(!sobol.point_into(index, &mut buf));
+        assert!(!fixed.point_into(index, &mut buf));
+        assert!(!padded.point_into(index, &mut buf));
+    }
+    assert_eq!(buf, [7; 3]);
+}
@@ -0,0 +1,68 @@
+use sobol_qmc::params::JoeKuoD6;
+use sobol_qmc::*;
+use std::sync::Arc;
+
+/// The number of dimensions of the sequences under test
+const DIMS: usize = 5;
+
+/// The number of points compared per test
+const N: usize = 1 << 8;
+
+#[test]
+fn test_streams() {
+    let params = JoeKuoD6::STANDARD;
+    let base = Sobol::<f64>::new(DIMS, &params).unwrap();
+    let factory = StreamFactory::new(base.clone(), 42);
+
+    // Streams are reproducible, distinct, and share the direction values of the base
+    let first: Vec<_> = factory.stream(3).take(N).collect();
+    assert_eq!(first, factory.stream(3).take(N).collect::<Vec<_>>());
+    assert!(Arc::ptr_eq(factory.stream(3).table(), base.table()));
+    for (id, stream) in factory.streams(0).take(8).enumerate() {
+        let points: Vec<_> = stream.take(N).collect();
+        assert_eq!(points == first, id == 3, "stream {id}");
+    }
+    assert_eq!(
+        factory.stream(7).take(N).collect::<Vec<_>>(),
+        base.clone()
+            .with_owen_scramble(factory.stream_seed(7))
+            .take(N)
+            .collect::<Vec<_>>()
+    );
+
+    // Different master seeds give different streams
+    let other = StreamFactory::new(base.clone(), 43);
+    assert_ne!(other.stream(3).take(N).collect::<Vec<_>>(), first);
+
+    let shifted = factory
+        .clone()
+        .with_randomization(Randomization::DigitalShift);
+    assert_eq!(factory.randomization(), Randomization::OwenScramble);
+    assert_eq!(shifted.randomization(), Randomization::DigitalShift);
+    assert_eq!(shifted.master_seed(), 42);
+    assert_eq!(
+        shifted.stream(3).take(N).collect::<Vec<_>>(),
+        base.with_digital_shift(factory.stream_seed(3))
+            .take(N)
+            .collect::<Vec<_>>()
+    );
+}
+
+/// Stream seeds are successive outputs of one generator, computed directly from the id
+#[test]
+fn test_stream_seeds() {
+    let params = JoeKuoD6::STANDARD;
+    let factory = StreamFactory::new(Sobol::<u32>::new(DIMS, &params).unwrap(), 0);
+    let mut seeds: Vec<_> = (0..1000).map(|id| factory.stream_seed(id)).collect();
+    seeds.sort();
+    seeds.dedup();
+    assert_eq!(seeds.len(), 1000);
+
+    let mut rng = SplitMix64::new(0x5eed);
+    let sequential: Vec<_> = (0..10).map(|_| rng.next_u64()).collect();
+    for (n, seed) in sequential.iter().enumerate() {
+        let mut jumped = SplitMix64::new(0x5eed);
+        jumped.skip(n as u64);
+        assert_eq!(jumped.next_u64(), *seed);
+    }
+}
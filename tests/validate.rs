@@ -1,9 +1,12 @@
+#![allow(clippy::approx_constant)]
+
 include!(concat!(env!("OUT_DIR"), "/gen_ref_seq.rs"));
 
 use sobol_qmc::params::JoeKuoD6;
 use sobol_qmc::*;
 
 use std::fmt::Display;
+use std::sync::Arc;
 
 /// Validate low-dimensional `f32` sequence
 #[test]
@@ -333,6 +336,336 @@ fn validate_i128_lores_hidim_skip() {
     }
 }
 
+/// Validate random access into a high-dimensional `f32` sequence
+#[test]
+fn validate_f32_hires_hidim_point_at() {
+    validate_point_at::<f32>(REF_SEQ_HI, None);
+}
+
+/// Validate random access into a high-dimensional `u64` sequence
+#[test]
+fn validate_u64_hires_hidim_point_at() {
+    validate_point_at::<u64>(REF_SEQ_HI, None);
+}
+
+/// Validate random access into a low-resolution low-dimensional `i16` sequence
+#[test]
+fn validate_i16_lores_lodim_point_at() {
+    validate_point_at::<i16>(REF_SEQ_LO, Some(11));
+}
+
+/// Natural and Gray code order cover the same points over power-of-two prefixes
+#[test]
+fn validate_natural_order_blocks() {
+    let sobol = Sobol::<u32>::new(8, &JoeKuoD6::STANDARD).unwrap();
+    for m in [1, 4, 6, 10] {
+        let block = |f: fn(&Sobol<u32>, u32) -> Option<Vec<u32>>| {
+            let mut points: Vec<_> = (0..1 << m).map(|i| f(&sobol, i).unwrap()).collect();
+            points.sort();
+            points
+        };
+        assert_eq!(block(Sobol::point_at), block(Sobol::point_at_natural));
+    }
+    assert_ne!(sobol.point_at(2), sobol.point_at_natural(2));
+    assert_eq!(sobol.point_at(sobol.max_len), None);
+    assert_eq!(sobol.point_at_natural(sobol.max_len), None);
+}
+
+/// Seeking repositions a sequence onto the same points as iterating to them
+#[test]
+fn validate_seek() {
+    let mut sobol = Sobol::<u32>::new(REF_SEQ_HI[0].len(), &JoeKuoD6::EXTENDED).unwrap();
+    for index in [700u32, 3, 0, 1, 255, 256, 42] {
+        sobol.seek(index);
+        assert_eq!(sobol.position(), index);
+        let point = sobol.next().unwrap();
+        assert_eq!(Some(point), sobol.point_at(index));
+        assert_eq!(sobol.position(), index + 1);
+    }
+
+    let mut skipped = sobol.clone().skip(1 << 30);
+    assert_eq!(skipped.next(), sobol.point_at(43 + (1 << 30)));
+
+    sobol.seek(u32::MAX);
+    assert_eq!(sobol.position(), sobol.max_len);
+    assert_eq!(sobol.next(), None);
+    assert_eq!(sobol.nth(usize::MAX), None);
+}
+
+/// Writing points into a reused buffer yields the same points as `next()`
+#[test]
+fn validate_next_into() {
+    let sobol =
+        Sobol::<f64>::new_with_resolution(6, &JoeKuoD6::MINIMAL, Some(8), UnitRender).unwrap();
+    let mut into = sobol.clone();
+    let mut buf = [0f64; 6];
+    for point in sobol {
+        assert!(into.next_into(&mut buf));
+        assert_eq!(point, buf);
+    }
+    assert!(!into.next_into(&mut buf));
+}
+
+/// Writing points into a buffer of the wrong length panics
+#[test]
+#[should_panic(expected = "buffer length must match")]
+fn validate_next_into_buf_len() {
+    let mut sobol = Sobol::<f32>::new(6, &JoeKuoD6::MINIMAL).unwrap();
+    sobol.next_into(&mut [0f32; 5]);
+}
+
+/// Blocks of points in either layout match the points yielded by `next()`
+#[test]
+fn validate_fill_block() {
+    const DIMS: usize = 5;
+    const N: usize = 13;
+    let sobol =
+        Sobol::<u16>::new_with_resolution(DIMS, &JoeKuoD6::MINIMAL, Some(6), UnitRender).unwrap();
+    let points: Vec<_> = sobol.clone().collect();
+
+    for layout in [Layout::PointMajor, Layout::DimensionMajor] {
+        let mut block = sobol.clone();
+        let mut buf = [0u16; N * DIMS];
+        let mut start = 0;
+        loop {
+            let len = block.fill_block(N, &mut buf, layout);
+            for (i, point) in points[start..start + len].iter().enumerate() {
+                for (dim, val) in point.iter().enumerate() {
+                    let pos = match layout {
+                        Layout::PointMajor => i * DIMS + dim,
+                        Layout::DimensionMajor => dim * N + i,
+                    };
+                    assert_eq!(buf[pos], *val);
+                }
+            }
+            start += len;
+            if len < N {
+                break;
+            }
+        }
+        assert_eq!(start, points.len());
+        assert_eq!(block.next(), None);
+    }
+
+    // Iteration resumes where a block left off
+    let mut block = sobol.clone();
+    block.fill_block(N, &mut [0u16; N * DIMS], Layout::DimensionMajor);
+    assert_eq!(block.next().as_ref(), points.get(N));
+
+    // Blocks of zero-dimensional points still advance the sequence
+    for layout in [Layout::PointMajor, Layout::DimensionMajor] {
+        let mut empty = Sobol::<u16>::new(0, &JoeKuoD6::MINIMAL).unwrap();
+        assert_eq!(empty.fill_block(N, &mut [], layout), N);
+        assert_eq!(empty.count, N as u16);
+    }
+}
+
+/// Natural order sequences yield, seek and fill blocks in natural order
+#[test]
+fn validate_natural_order() {
+    const DIMS: usize = 7;
+    let sobol = Sobol::<u32>::new_with_resolution(DIMS, &JoeKuoD6::MINIMAL, Some(9), UnitRender)
+        .unwrap()
+        .with_ordering(PointOrder::Natural);
+    assert_eq!(sobol.ordering(), PointOrder::Natural);
+
+    let points: Vec<_> = sobol.clone().collect();
+    assert_eq!(points.len(), (1 << 9) - 1);
+    for (i, point) in points.iter().enumerate() {
+        assert_eq!(Some(point), sobol.point_at_natural(i as u32).as_ref());
+        assert_eq!(Some(point), sobol.point_at(i as u32).as_ref());
+    }
+
+    let mut seeked = sobol.clone();
+    seeked.seek(100);
+    assert_eq!(seeked.next().as_ref(), points.get(100));
+
+    let mut block = sobol.clone();
+    let mut buf = [0u32; 30 * DIMS];
+    block.seek(3);
+    block.fill_block(30, &mut buf, Layout::DimensionMajor);
+    for (i, point) in points[3..33].iter().enumerate() {
+        for (dim, val) in point.iter().enumerate() {
+            assert_eq!(buf[dim * 30 + i], *val);
+        }
+    }
+    assert_eq!(block.next().as_ref(), points.get(33));
+}
+
+/// Sequences report their exact remaining length
+#[test]
+fn validate_size_hint() {
+    let mut sobol =
+        Sobol::<u8>::new_with_resolution(3, &JoeKuoD6::MINIMAL, Some(6), UnitRender).unwrap();
+    assert_eq!(sobol.len(), 63);
+    sobol.nth(9);
+    assert_eq!(sobol.size_hint(), (53, Some(53)));
+    assert_eq!(sobol.by_ref().count(), 53);
+    assert_eq!(sobol.len(), 0);
+    assert_eq!(sobol.next(), None);
+
+    let sobol = Sobol::<u128>::new(3, &JoeKuoD6::MINIMAL).unwrap();
+    assert_eq!(sobol.size_hint(), (usize::MAX, None));
+    assert_eq!(sobol.range(7..19).size_hint(), (12, Some(12)));
+}
+
+/// Walking a sequence backwards yields the forward points in reverse
+#[test]
+fn validate_double_ended() {
+    for ordering in [PointOrder::Gray, PointOrder::Natural] {
+        let sobol = Sobol::<u32>::new_with_resolution(9, &JoeKuoD6::MINIMAL, Some(7), UnitRender)
+            .unwrap()
+            .with_ordering(ordering);
+        let forward: Vec<_> = sobol.clone().collect();
+        let backward: Vec<_> = sobol.clone().rev().collect();
+        assert_eq!(forward.len(), backward.len());
+        assert!(forward.iter().eq(backward.iter().rev()));
+
+        let range: Vec<_> = sobol.clone().range(20..45).rev().collect();
+        assert!(range.iter().eq(forward[20..45].iter().rev()));
+
+        // Both ends meet without overlapping
+        let mut both = sobol.clone().range(5..12);
+        assert_eq!(both.next().as_ref(), forward.get(5));
+        assert_eq!(both.next_back().as_ref(), forward.get(11));
+        assert_eq!(both.nth_back(2).as_ref(), forward.get(8));
+        assert_eq!(both.len(), 2);
+        assert_eq!(both.nth(1).as_ref(), forward.get(7));
+        assert_eq!(both.next_back(), None);
+        assert_eq!(both.next(), None);
+    }
+}
+
+/// Splitting a sequence into index ranges reproduces its serial output
+#[test]
+fn validate_split() {
+    let sobol = Sobol::<f32>::new_with_resolution(4, &JoeKuoD6::MINIMAL, Some(8), UnitRender)
+        .unwrap()
+        .range(3..250);
+    let serial: Vec<_> = sobol.clone().collect();
+
+    let (head, tail) = sobol.clone().split_at(100);
+    assert_eq!((head.len(), tail.len()), (97, 150));
+    assert!(head.chain(tail).eq(serial.iter().cloned()));
+
+    for n in [1, 2, 7, 246, 247, 300] {
+        let chunks = sobol.clone().chunks(n);
+        assert_eq!(chunks.len(), n);
+        let lens: Vec<_> = chunks.iter().map(|c| c.len()).collect();
+        assert!(lens.iter().max().unwrap() - lens.iter().min().unwrap() <= 1);
+        assert!(chunks.into_iter().flatten().eq(serial.iter().cloned()));
+    }
+}
+
+/// Cached direction tables are shared between sequences and generate the same points
+#[test]
+fn validate_cached_table() {
+    let params = JoeKuoD6::EXTENDED;
+    let dims = REF_SEQ_HI[0].len();
+    let table = DirectionTable::<f64>::cached(dims, &params, None).unwrap();
+    assert!(Arc::ptr_eq(
+        &table,
+        &DirectionTable::cached(dims, &params, None).unwrap()
+    ));
+    assert!(!Arc::ptr_eq(
+        &table,
+        &DirectionTable::cached(dims, &params, Some(20)).unwrap()
+    ));
+    assert!(!Arc::ptr_eq(
+        &table,
+        &DirectionTable::cached(dims - 1, &params, None).unwrap()
+    ));
+    // Keys do not depend on where the constant's data lives
+    assert!(Arc::ptr_eq(
+        &table,
+        &DirectionTable::cached(dims, &JoeKuoD6::EXTENDED, None).unwrap()
+    ));
+    // Parameters which do not borrow from the embedded table are not cached
+    let copied = JoeKuoD6 {
+        dim_params: params.dim_params.to_vec().leak(),
+        ..params
+    };
+    assert!(!Arc::ptr_eq(
+        &DirectionTable::<f64>::cached(dims, &copied, None).unwrap(),
+        &DirectionTable::cached(dims, &copied, None).unwrap()
+    ));
+
+    let sobol = Sobol::from_table(table.clone(), UnitRender).unwrap();
+    assert!(Arc::ptr_eq(sobol.table(), &table));
+    assert!(Arc::ptr_eq(sobol.clone().table(), &table));
+    let uncached = Sobol::<f64>::new(dims, &params).unwrap();
+    assert!(sobol.take(1000).eq(uncached.take(1000)));
+
+    assert!(matches!(
+        DirectionTable::<f64>::cached(params.max_dims + 1, &params, None),
+        Err(SobolError::MaxDim { .. })
+    ));
+}
+
+/// Fixed-dimension sequences yield the same points as their dynamic counterparts
+#[test]
+fn validate_fixed_dims() {
+    let params = JoeKuoD6::MINIMAL;
+    let sobol = Sobol::<f32>::new(3, &params).unwrap();
+    let fixed = SobolN::<f32, 3>::new(&params).unwrap();
+    assert!(
+        fixed
+            .clone()
+            .take(5000)
+            .map(Vec::from)
+            .eq(sobol.clone().take(5000))
+    );
+    assert_eq!(fixed.clone().nth(777).map(Vec::from), sobol.point_at(777));
+    assert_eq!(fixed.point_at(12345).map(Vec::from), sobol.point_at(12345));
+
+    let lores = SobolN::<u16, 2>::new_with_resolution(&params, Some(5), UnitRender).unwrap();
+    assert_eq!(lores.len(), 31);
+    let sobol = Sobol::<u16>::new_with_resolution(2, &params, Some(5), UnitRender).unwrap();
+    assert!(lores.map(Vec::from).eq(sobol));
+
+    // Direction values are stored once per bit of resolution, off the generator itself
+    let wide = SobolN::<u128, 1000>::new(&JoeKuoD6::STANDARD).unwrap();
+    assert!(std::mem::size_of_val(&wide) < 1000 * 16 + 128);
+    let sobol = Sobol::<u128>::new(1000, &JoeKuoD6::STANDARD).unwrap();
+    assert_eq!(wide.point_at(4321).map(Vec::from), sobol.point_at(4321));
+
+    assert!(matches!(
+        SobolN::<f64, 101>::new(&params),
+        Err(SobolError::MaxDim {
+            dims: 101,
+            max_dims: 100
+        })
+    ));
+}
+
+/// Sequences built from a subset of parameter dimensions match the corresponding components
+/// of the full sequence
+#[test]
+fn validate_dim_subset() {
+    let params = JoeKuoD6::STANDARD;
+    let full = Sobol::<u32>::new(40, &params).unwrap();
+    for dims in [
+        vec![1, 2, 7, 40],
+        vec![40, 7, 1, 2],
+        (5..15).collect(),
+        vec![3, 3],
+    ] {
+        let subset = Sobol::<u32>::new_with_dims(dims.clone(), &params, None, UnitRender).unwrap();
+        assert_eq!(subset.dims, dims.len());
+        let expected = full
+            .clone()
+            .map(|p| dims.iter().map(|d| p[d - 1]).collect::<Vec<_>>());
+        assert!(subset.take(2000).eq(expected.take(2000)));
+    }
+
+    for dim in [0, params.max_dims + 1] {
+        assert!(matches!(
+            Sobol::<u32>::new_with_dims([1, dim], &params, None, UnitRender),
+            Err(SobolError::InvalidDim { dim: d, max_dims: 1000 }) if d == dim
+        ));
+    }
+}
+
 /// Generates a sequence of type T and compares values to an externally generated
 /// reference sequence (see 'test/data/ref_seq_*.tsv.gz')
 fn validate<T>(ref_seq: &[&[f32]], resolution: Option<usize>)
@@ -344,15 +677,21 @@ where
     let dims: usize = ref_seq[0].len();
     let params = JoeKuoD6::EXTENDED;
 
-    Sobol::<T>::new_with_resolution(dims, &params, resolution,UnitRender).unwrap()
-        .map(|s| s.iter().map(|v| v.to_float()).collect::<Vec<_>>())
-        .zip(ref_seq.iter().map(|p| p.to_vec()).collect::<Vec<_>>())
-        .enumerate()
-        .filter(|(_, (s, r))| s != r)
-        .take(1)
-        .for_each(|(i, (s, r))| {
-            panic!("Generated point #{} does not match point from reference sequence!\n  --> generated = {}\n  --> expected =  {}", i, point_str(&s), point_str(&r));
-        });
+    if let Some((i, (s, r))) =
+        Sobol::<T>::new_with_resolution(dims, &params, resolution, UnitRender)
+            .unwrap()
+            .map(|s| s.iter().map(|v| v.to_float()).collect::<Vec<_>>())
+            .zip(ref_seq.iter().map(|p| p.to_vec()).collect::<Vec<_>>())
+            .enumerate()
+            .find(|(_, (s, r))| s != r)
+    {
+        panic!(
+            "Generated point #{} does not match point from reference sequence!\n  --> generated = {}\n  --> expected =  {}",
+            i,
+            point_str(&s),
+            point_str(&r)
+        );
+    }
 }
 
 /// Generates a sequence of type T and compares values to an externally generated
@@ -366,20 +705,68 @@ where
     let dims: usize = ref_seq[0].len();
     let params = JoeKuoD6::EXTENDED;
 
-    Sobol::<T>::new_with_resolution(dims, &params, resolution,UnitRender).unwrap()
-        .skip(skip)
-        .map(|s| s.iter().map(|v| v.to_float()).collect::<Vec<_>>())
-        .zip(ref_seq.iter().skip(skip).map(|p| p.to_vec()).collect::<Vec<_>>())
-        .enumerate()
-        .filter(|(_, (s, r))| s != r)
-        .take(1)
-        .for_each(|(i, (s, r))| {
-            panic!("Generated point #{} does not match point from reference sequence with skip {}!\n  --> generated = {}\n  --> expected =  {}", i, skip, point_str(&s), point_str(&r));
-        });
+    if let Some((i, (s, r))) =
+        Sobol::<T>::new_with_resolution(dims, &params, resolution, UnitRender)
+            .unwrap()
+            .skip(skip)
+            .map(|s| s.iter().map(|v| v.to_float()).collect::<Vec<_>>())
+            .zip(
+                ref_seq
+                    .iter()
+                    .skip(skip)
+                    .map(|p| p.to_vec())
+                    .collect::<Vec<_>>(),
+            )
+            .enumerate()
+            .find(|(_, (s, r))| s != r)
+    {
+        panic!(
+            "Generated point #{} does not match point from reference sequence with skip {}!\n  --> generated = {}\n  --> expected =  {}",
+            i,
+            skip,
+            point_str(&s),
+            point_str(&r)
+        );
+    }
+}
+
+/// Computes points of type T by index and compares values to an externally generated
+/// reference sequence (see 'test/data/ref_seq_*.tsv.gz')
+fn validate_point_at<T>(ref_seq: &[&[f32]], resolution: Option<usize>)
+where
+    T: SobolType + ToFloat + Display + Default + Clone,
+    T::IT: LossyFrom<u32>,
+    UnitRender: Render<T>,
+{
+    let dims: usize = ref_seq[0].len();
+    let params = JoeKuoD6::EXTENDED;
+    let sobol = Sobol::<T>::new_with_resolution(dims, &params, resolution, UnitRender).unwrap();
+    let mut buf = vec![T::default(); dims];
+
+    // Visit the reference points in reverse so no state can carry over between lookups
+    for (i, r) in ref_seq.iter().enumerate().rev() {
+        let index = T::IT::lossy_from(i as u32);
+        let Some(point) = sobol.point_at(index) else {
+            // Low-resolution sequences end before the reference sequence does
+            assert!(index >= sobol.max_len && !sobol.point_at_into(index, &mut buf));
+            continue;
+        };
+        assert!(sobol.point_at_into(index, &mut buf));
+        assert_eq!(point_str(&point), point_str(&buf));
+        let s: Vec<_> = point.iter().map(|v| v.to_float()).collect();
+        if s != *r {
+            panic!(
+                "Point #{} computed by index does not match point from reference sequence!\n  --> generated = {}\n  --> expected =  {}",
+                i,
+                point_str(&s),
+                point_str(r)
+            );
+        }
+    }
 }
 
 /// Generates string representation of a multi-dimensional point for display
-fn point_str<T: Display>(point: &Vec<T>) -> String {
+fn point_str<T: Display>(point: &[T]) -> String {
     format!(
         "[{}]",
         point